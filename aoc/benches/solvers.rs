@@ -0,0 +1,67 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+
+use common::Solver;
+
+/// Benchmarks parsing and both parts of a day, on its bundled input and on each of the `samples`.
+fn bench_day<S: Solver>(c: &mut Criterion, samples: &[(&str, &str)]) {
+    let mut group = c.benchmark_group(format!("day{}", S::DAY));
+    let inputs = [("input", S::INPUT)]
+        .into_iter()
+        .chain(samples.iter().copied());
+
+    for (name, input) in inputs {
+        group.bench_with_input(BenchmarkId::new("parse", name), input, |b, input| {
+            b.iter(|| S::parse(black_box(input)).unwrap())
+        });
+
+        let parsed = S::parse(input).unwrap();
+        group.bench_with_input(BenchmarkId::new("first", name), &parsed, |b, parsed| {
+            b.iter(|| S::first(black_box(parsed)))
+        });
+        group.bench_with_input(BenchmarkId::new("second", name), &parsed, |b, parsed| {
+            b.iter(|| S::second(black_box(parsed)))
+        });
+    }
+
+    group.finish();
+}
+
+macro_rules! sample {
+    ($day:literal) => {
+        sample!($day, "test")
+    };
+    ($day:literal, $name:literal) => {
+        (
+            $name,
+            include_str!(concat!("../../", $day, "/src/", $name, ".txt")),
+        )
+    };
+}
+
+fn days(c: &mut Criterion) {
+    bench_day::<day1::Day1>(c, &[sample!("day1")]);
+    bench_day::<day2::Day2>(c, &[sample!("day2")]);
+    bench_day::<day3::Day3>(c, &[sample!("day3")]);
+    bench_day::<day4::Day4>(c, &[sample!("day4")]);
+    bench_day::<day5::Day5>(c, &[sample!("day5")]);
+    bench_day::<day6::Day6>(c, &[sample!("day6")]);
+    bench_day::<day7::Day7>(c, &[sample!("day7")]);
+    bench_day::<day8::Day8>(c, &[sample!("day8")]);
+    bench_day::<day9::Day9>(c, &[sample!("day9")]);
+    bench_day::<day10::Day10>(c, &[sample!("day10")]);
+    bench_day::<day11::Day11>(c, &[sample!("day11")]);
+    bench_day::<day12::Day12>(
+        c,
+        &[
+            sample!("day12", "test1"),
+            sample!("day12", "test2"),
+            sample!("day12", "test3"),
+        ],
+    );
+    bench_day::<day13::Day13>(c, &[sample!("day13")]);
+    bench_day::<day14::Day14>(c, &[sample!("day14")]);
+    bench_day::<day15::Day15>(c, &[sample!("day15")]);
+}
+
+criterion_group!(benches, days);
+criterion_main!(benches);
@@ -0,0 +1,465 @@
+use std::{
+    borrow::Cow,
+    env,
+    error::Error,
+    fmt::Display,
+    fs, io,
+    ops::RangeInclusive,
+    path::PathBuf,
+    process,
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+use common::Solver;
+use rayon::prelude::*;
+use report::{Format, Report};
+use verify::{Answers, Verdict};
+
+mod report;
+mod verify;
+
+type Solve = fn(&str, Part) -> Result<Outcome, Box<dyn Error>>;
+
+struct Day {
+    input: &'static str,
+    solve: Solve,
+}
+const fn day<S: Solver>() -> Day {
+    Day {
+        input: S::INPUT,
+        solve: solve::<S>,
+    }
+}
+
+const DAYS: [Day; 15] = [
+    day::<day1::Day1>(),
+    day::<day2::Day2>(),
+    day::<day3::Day3>(),
+    day::<day4::Day4>(),
+    day::<day5::Day5>(),
+    day::<day6::Day6>(),
+    day::<day7::Day7>(),
+    day::<day8::Day8>(),
+    day::<day9::Day9>(),
+    day::<day10::Day10>(),
+    day::<day11::Day11>(),
+    day::<day12::Day12>(),
+    day::<day13::Day13>(),
+    day::<day14::Day14>(),
+    day::<day15::Day15>(),
+];
+
+const USAGE: &str = "Usage: aoc [all | <day> | <first>-<last>] [--part <1|2>] \
+[--input <file> | --inputs <directory>] [--format <table|json|csv>] [--budget <ms>]
+
+--input <file>        read the input of a single day from <file>, or stdin if <file> is `-`
+--inputs <directory>  read the input of each day from <directory>/day<day>.txt
+--format <format>     print the answers and timings as a table (default), JSON or CSV
+--budget <ms>         fail if parsing and solving take more than <ms> milliseconds in total
+--verify              check the answers against the bundled answers.toml instead of printing them
+--answers <file>      check the answers against <file> instead of the bundled answers.toml";
+
+fn main() {
+    let args = match Args::parse(env::args().skip(1)) {
+        Ok(args) => args,
+        Err(err) => {
+            eprintln!("{}\n{}", err, USAGE);
+            process::exit(2);
+        }
+    };
+
+    let inputs = args
+        .days
+        .clone()
+        .map(|day| match args.input.read(day) {
+            Ok(input) => (day, input),
+            Err(err) => {
+                eprintln!("Day {}: {}", day, err);
+                process::exit(1);
+            }
+        })
+        .collect::<Vec<_>>();
+    let jobs = inputs
+        .iter()
+        .flat_map(|(day, input)| {
+            args.parts
+                .iter()
+                .map(move |part| (*day, input.as_ref(), *part))
+        })
+        .collect::<Vec<_>>();
+
+    let start = Instant::now();
+    let results = solve_all(jobs);
+    let elapsed = start.elapsed();
+
+    let mut outcomes = Vec::with_capacity(results.len());
+    let mut failed = false;
+    for result in results {
+        match result {
+            Ok(outcome) => outcomes.push(outcome),
+            Err(err) => {
+                eprintln!("{}", err);
+                failed = true;
+            }
+        }
+    }
+
+    let report = Report { outcomes, elapsed };
+    match &args.verify {
+        Some(source) => {
+            let answers = match source {
+                AnswersSource::Bundled => Answers::bundled(),
+                AnswersSource::File(path) => Answers::read(path).unwrap_or_else(|err| {
+                    eprintln!("{}", err);
+                    process::exit(1);
+                }),
+            };
+            failed |= !print_verdicts(&report, &answers);
+        }
+        None => print!("{}", report.render(args.format)),
+    }
+    if let Some(budget) = args.budget {
+        if report.total() > budget {
+            eprintln!(
+                "Total time {:.2?} is over the budget of {:.2?}",
+                report.total(),
+                budget
+            );
+            failed = true;
+        }
+    }
+    if failed {
+        process::exit(1);
+    }
+}
+
+/// Solves each `(day, input, part)` in parallel, keeping the order of `jobs`.
+fn solve_all(jobs: Vec<(u8, &str, Part)>) -> Vec<Result<Outcome, String>> {
+    jobs.into_par_iter()
+        .map(|(day, input, part)| {
+            (DAYS[usize::from(day) - 1].solve)(input, part)
+                .map_err(|err| format!("Day {} {}: {}", day, part, err))
+        })
+        .collect()
+}
+
+/// Prints whether each answer of `report` matches `answers`, returning `false` if any does not.
+fn print_verdicts(report: &Report, answers: &Answers) -> bool {
+    let (mut passed, mut failed, mut missing) = (0, 0, 0);
+    for outcome in &report.outcomes {
+        let verdict = answers.verify(outcome);
+        println!("Day {:>2} {}: {}", outcome.day, outcome.part, verdict);
+        match verdict {
+            Verdict::Pass => passed += 1,
+            Verdict::Fail { expected } => {
+                println!(
+                    "  expected: {}",
+                    expected.trim_end().replace('\n', "\n            ")
+                );
+                println!(
+                    "     found: {}",
+                    outcome.answer.trim_end().replace('\n', "\n            ")
+                );
+                failed += 1;
+            }
+            Verdict::Missing => missing += 1,
+        }
+    }
+    println!("{} passed, {} failed, {} missing", passed, failed, missing);
+
+    failed == 0
+}
+
+fn solve<S: Solver>(input: &str, part: Part) -> Result<Outcome, Box<dyn Error>> {
+    let start = Instant::now();
+    let input = S::parse(input)?;
+    let parse = start.elapsed();
+
+    let start = Instant::now();
+    let answer = match part {
+        Part::First => S::first(&input)?.to_string(),
+        Part::Second => S::second(&input)?.to_string(),
+    };
+    let solve = start.elapsed();
+
+    Ok(Outcome {
+        day: S::DAY,
+        part,
+        answer,
+        parse,
+        solve,
+    })
+}
+
+struct Outcome {
+    day: u8,
+    part: Part,
+    answer: String,
+    parse: Duration,
+    solve: Duration,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Part {
+    First,
+    Second,
+}
+impl Part {
+    fn number(self) -> u8 {
+        match self {
+            Part::First => 1,
+            Part::Second => 2,
+        }
+    }
+}
+impl Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Part::First => write!(f, "part 1"),
+            Part::Second => write!(f, "part 2"),
+        }
+    }
+}
+impl FromStr for Part {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1" => Ok(Part::First),
+            "2" => Ok(Part::Second),
+            _ => Err(format!("Invalid part: {}", s)),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Input {
+    Bundled,
+    File(String),
+    Directory(PathBuf),
+}
+impl Input {
+    fn read(&self, day: u8) -> io::Result<Cow<'static, str>> {
+        let bundled = DAYS[usize::from(day) - 1].input;
+        match self {
+            Input::Bundled => Ok(Cow::Borrowed(bundled)),
+            Input::File(path) => common::read_input(Some(path), bundled),
+            Input::Directory(dir) => {
+                let path = dir.join(format!("day{}.txt", day));
+                fs::read_to_string(&path).map(Cow::Owned).map_err(|err| {
+                    io::Error::new(err.kind(), format!("{}: {}", path.display(), err))
+                })
+            }
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum AnswersSource {
+    Bundled,
+    File(PathBuf),
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct Args {
+    days: RangeInclusive<u8>,
+    parts: Vec<Part>,
+    input: Input,
+    format: Format,
+    budget: Option<Duration>,
+    verify: Option<AnswersSource>,
+}
+impl Args {
+    fn parse(mut args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let mut days = None;
+        let mut parts = vec![Part::First, Part::Second];
+        let mut input = Input::Bundled;
+        let mut format = Format::Table;
+        let mut budget = None;
+        let mut verify = None;
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--part" | "-p" => {
+                    let part = args.next().ok_or_else(|| "Missing part".to_string())?;
+                    parts = vec![part.parse()?];
+                }
+                "--input" | "-i" => {
+                    let path = args
+                        .next()
+                        .ok_or_else(|| "Missing input file".to_string())?;
+                    input = Input::File(path);
+                }
+                "--inputs" => {
+                    let dir = args
+                        .next()
+                        .ok_or_else(|| "Missing input directory".to_string())?;
+                    input = Input::Directory(dir.into());
+                }
+                "--format" | "-f" => {
+                    let name = args.next().ok_or_else(|| "Missing format".to_string())?;
+                    format = name.parse()?;
+                }
+                "--budget" => {
+                    let millis = args.next().ok_or_else(|| "Missing budget".to_string())?;
+                    let millis = millis
+                        .parse()
+                        .map_err(|_| format!("Invalid budget: {}", millis))?;
+                    budget = Some(Duration::from_millis(millis));
+                }
+                "--verify" => {
+                    verify.get_or_insert(AnswersSource::Bundled);
+                }
+                "--answers" => {
+                    let path = args
+                        .next()
+                        .ok_or_else(|| "Missing answers file".to_string())?;
+                    verify = Some(AnswersSource::File(path.into()));
+                }
+                _ if days.is_none() => days = Some(parse_days(&arg)?),
+                _ => return Err(format!("Unexpected argument: {}", arg)),
+            }
+        }
+
+        let days = days.unwrap_or(1..=DAYS.len() as u8);
+        if matches!(input, Input::File(_)) && days.start() != days.end() {
+            return Err("--input can only be used when running a single day".to_string());
+        }
+
+        Ok(Self {
+            days,
+            parts,
+            input,
+            format,
+            budget,
+            verify,
+        })
+    }
+}
+
+fn parse_days(s: &str) -> Result<RangeInclusive<u8>, String> {
+    let parse_day = |day: &str| match day.trim().parse::<u8>() {
+        Ok(day) if (1..=DAYS.len() as u8).contains(&day) => Ok(day),
+        _ => Err(format!("Invalid day: {}", day)),
+    };
+
+    if s == "all" {
+        Ok(1..=DAYS.len() as u8)
+    } else if let Some((first, last)) = s.split_once('-') {
+        let (first, last) = (parse_day(first)?, parse_day(last)?);
+        if first > last {
+            return Err(format!("Invalid range of days: {}", s));
+        }
+        Ok(first..=last)
+    } else {
+        let day = parse_day(s)?;
+        Ok(day..=day)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::{solve_all, Answers, AnswersSource, Args, Format, Input, Part, Verdict, DAYS};
+
+    fn parse(args: &[&str]) -> Result<Args, String> {
+        Args::parse(args.iter().map(ToString::to_string))
+    }
+
+    #[test]
+    fn parse_args() {
+        assert_eq!(
+            parse(&[]).unwrap(),
+            Args {
+                days: 1..=15,
+                parts: vec![Part::First, Part::Second],
+                input: Input::Bundled,
+                format: Format::Table,
+                budget: None,
+                verify: None,
+            }
+        );
+        assert_eq!(
+            parse(&["4", "--part", "2"]).unwrap(),
+            Args {
+                days: 4..=4,
+                parts: vec![Part::Second],
+                input: Input::Bundled,
+                format: Format::Table,
+                budget: None,
+                verify: None,
+            }
+        );
+        assert_eq!(parse(&["3-7"]).unwrap().days, 3..=7);
+        assert!(parse(&["0"]).is_err());
+        assert!(parse(&["16"]).is_err());
+        assert!(parse(&["7-3"]).is_err());
+        assert!(parse(&["1", "--part", "3"]).is_err());
+
+        assert_eq!(
+            parse(&["4", "--input", "-"]).unwrap().input,
+            Input::File("-".to_string())
+        );
+        assert_eq!(
+            parse(&["--inputs", "inputs/alice"]).unwrap().input,
+            Input::Directory("inputs/alice".into())
+        );
+        assert!(parse(&["all", "--input", "day4.txt"]).is_err());
+
+        let args = parse(&["--format", "json", "--budget", "250"]).unwrap();
+        assert_eq!(
+            (args.format, args.budget),
+            (Format::Json, Some(Duration::from_millis(250)))
+        );
+        assert!(parse(&["--format", "xml"]).is_err());
+        assert!(parse(&["--budget", "soon"]).is_err());
+
+        assert_eq!(
+            parse(&["--verify"]).unwrap().verify,
+            Some(AnswersSource::Bundled)
+        );
+        assert_eq!(
+            parse(&["--answers", "answers.toml", "--verify"])
+                .unwrap()
+                .verify,
+            Some(AnswersSource::File("answers.toml".into()))
+        );
+    }
+
+    #[test]
+    fn bundled_answers() {
+        let answers = Answers::bundled();
+        let jobs = (1..=DAYS.len() as u8)
+            .flat_map(|day| {
+                let input = DAYS[usize::from(day) - 1].input;
+                [(day, input, Part::First), (day, input, Part::Second)]
+            })
+            .collect();
+
+        for outcome in solve_all(jobs) {
+            let outcome = outcome.unwrap();
+            assert_eq!(
+                answers.verify(&outcome),
+                Verdict::Pass,
+                "day {} {}: {}",
+                outcome.day,
+                outcome.part,
+                outcome.answer
+            );
+        }
+    }
+
+    #[test]
+    fn solver_errors() {
+        // the aim of part 2 makes the depth overflow, while part 1 stays within range
+        let course = "repeat 8 {\ndown 2147483647\n}\nforward 2147483647\n";
+        let outcomes = solve_all(vec![(2, course, Part::First), (2, course, Part::Second)]);
+        assert!(outcomes[0].is_ok());
+        assert_eq!(
+            outcomes[1].as_ref().err().unwrap(),
+            "Day 2 part 2: line 4: Command 9 `forward 2147483647` overflows"
+        );
+    }
+}
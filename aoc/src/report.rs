@@ -0,0 +1,204 @@
+use std::{str::FromStr, time::Duration};
+
+use serde_json::json;
+
+use crate::Outcome;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Table,
+    Json,
+    Csv,
+}
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "table" => Ok(Format::Table),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            _ => Err(format!("Invalid format: {}", s)),
+        }
+    }
+}
+
+/// The outcomes of a run of the solvers, in order of day and part.
+pub struct Report {
+    pub outcomes: Vec<Outcome>,
+    /// wall clock time of the whole run, shorter than the total when days run in parallel
+    pub elapsed: Duration,
+}
+impl Report {
+    pub fn parse_time(&self) -> Duration {
+        self.outcomes.iter().map(|outcome| outcome.parse).sum()
+    }
+    pub fn solve_time(&self) -> Duration {
+        self.outcomes.iter().map(|outcome| outcome.solve).sum()
+    }
+    pub fn total(&self) -> Duration {
+        self.parse_time() + self.solve_time()
+    }
+    pub fn render(&self, format: Format) -> String {
+        match format {
+            Format::Table => self.table(),
+            Format::Json => self.json(),
+            Format::Csv => self.csv(),
+        }
+    }
+    fn table(&self) -> String {
+        const HEADER: [&str; 5] = ["Day", "Part", "Answer", "Parse", "Solve"];
+
+        let answer_width = self
+            .outcomes
+            .iter()
+            .flat_map(|outcome| outcome.answer.lines())
+            .map(|line| line.chars().count())
+            .chain([HEADER[2].len()])
+            .max()
+            .unwrap_or(0);
+        let row = |[day, part, answer, parse, solve]: [&str; 5]| {
+            let row = format!(
+                "{:>5}  {:>4}  {:<answer_width$}  {:>10}  {:>10}",
+                day,
+                part,
+                answer,
+                parse,
+                solve,
+                answer_width = answer_width
+            );
+            row.trim_end().to_string() + "\n"
+        };
+
+        let mut table = row(HEADER);
+        for outcome in &self.outcomes {
+            let mut lines = outcome.answer.lines();
+            table += &row([
+                &outcome.day.to_string(),
+                &outcome.part.number().to_string(),
+                lines.next().unwrap_or(""),
+                &format!("{:.2?}", outcome.parse),
+                &format!("{:.2?}", outcome.solve),
+            ]);
+            // multi-line answers such as day 13's folded paper continue on the following rows
+            for line in lines {
+                table += &row(["", "", line, "", ""]);
+            }
+        }
+        table += &row([
+            "Total",
+            "",
+            "",
+            &format!("{:.2?}", self.parse_time()),
+            &format!("{:.2?}", self.solve_time()),
+        ]);
+        table += &format!(
+            "Elapsed {:.2?} (rayon threads: {})\n",
+            self.elapsed,
+            rayon::current_num_threads()
+        );
+        table
+    }
+    fn json(&self) -> String {
+        let outcomes: Vec<_> = self
+            .outcomes
+            .iter()
+            .map(|outcome| {
+                json!({
+                    "day": outcome.day,
+                    "part": outcome.part.number(),
+                    "answer": outcome.answer,
+                    "parse_ns": nanos(outcome.parse),
+                    "solve_ns": nanos(outcome.solve),
+                })
+            })
+            .collect();
+
+        let report = json!({
+            "outcomes": outcomes,
+            "parse_ns": nanos(self.parse_time()),
+            "solve_ns": nanos(self.solve_time()),
+            "total_ns": nanos(self.total()),
+            "elapsed_ns": nanos(self.elapsed),
+        });
+        format!("{:#}\n", report)
+    }
+    fn csv(&self) -> String {
+        let mut csv = "day,part,answer,parse_ns,solve_ns\n".to_string();
+        for outcome in &self.outcomes {
+            csv += &format!(
+                "{},{},{},{},{}\n",
+                outcome.day,
+                outcome.part.number(),
+                csv_field(&outcome.answer),
+                nanos(outcome.parse),
+                nanos(outcome.solve)
+            );
+        }
+        csv
+    }
+}
+
+fn nanos(duration: Duration) -> u64 {
+    duration.as_nanos().try_into().unwrap_or(u64::MAX)
+}
+
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::{
+        report::{Format, Report},
+        Outcome, Part,
+    };
+
+    fn report() -> Report {
+        let outcome = |day, part, answer: &str, micros| Outcome {
+            day,
+            part,
+            answer: answer.to_string(),
+            parse: Duration::from_micros(micros),
+            solve: Duration::from_micros(2 * micros),
+        };
+        Report {
+            outcomes: vec![
+                outcome(1, Part::First, "1162", 10),
+                outcome(13, Part::Second, "# #\n ##", 20),
+            ],
+            elapsed: Duration::from_micros(50),
+        }
+    }
+
+    #[test]
+    fn table() {
+        let table = report().render(Format::Table);
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines[0], "  Day  Part  Answer       Parse       Solve");
+        assert_eq!(lines[1], "    1     1  1162       10.00µs     20.00µs");
+        assert_eq!(lines[2], "   13     2  # #        20.00µs     40.00µs");
+        assert_eq!(lines[3], "              ##");
+        assert_eq!(lines[4], "Total                   30.00µs     60.00µs");
+        assert_eq!(report().total(), Duration::from_micros(90));
+    }
+
+    #[test]
+    fn json_and_csv() {
+        let json: serde_json::Value = serde_json::from_str(&report().render(Format::Json)).unwrap();
+        assert_eq!(json["outcomes"][1]["answer"], "# #\n ##");
+        assert_eq!(json["outcomes"][1]["solve_ns"], 40_000);
+        assert_eq!(json["total_ns"], 90_000);
+
+        assert_eq!(
+            report().render(Format::Csv),
+            "day,part,answer,parse_ns,solve_ns\n1,1,1162,10000,20000\n13,2,\"# #\n ##\",20000,40000\n"
+        );
+    }
+}
@@ -0,0 +1,107 @@
+use std::{fmt::Display, fs, path::Path, str::FromStr};
+
+use crate::{Outcome, Part};
+
+const BUNDLED: &str = include_str!("../../answers.toml");
+
+/// The expected answers of each day, from a TOML file with a `[day<day>]` table holding the
+/// `part1` and `part2` answers.
+pub struct Answers(toml::Table);
+impl Answers {
+    /// The answers to the bundled inputs, from `answers.toml` at the root of the workspace.
+    pub fn bundled() -> Self {
+        BUNDLED.parse().expect("answers.toml is valid")
+    }
+    pub fn read(path: &Path) -> Result<Self, String> {
+        let answers =
+            fs::read_to_string(path).map_err(|err| format!("{}: {}", path.display(), err))?;
+        answers
+            .parse()
+            .map_err(|err| format!("{}: {}", path.display(), err))
+    }
+    pub fn get(&self, day: u8, part: Part) -> Option<String> {
+        let answer = self
+            .0
+            .get(&format!("day{}", day))?
+            .get(format!("part{}", part.number()))?;
+        match answer {
+            toml::Value::String(answer) => Some(answer.clone()),
+            answer => Some(answer.to_string()),
+        }
+    }
+    pub fn verify(&self, outcome: &Outcome) -> Verdict {
+        match self.get(outcome.day, outcome.part) {
+            None => Verdict::Missing,
+            Some(expected) if normalize(&expected) == normalize(&outcome.answer) => Verdict::Pass,
+            Some(expected) => Verdict::Fail { expected },
+        }
+    }
+}
+impl FromStr for Answers {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse().map(Self).map_err(|err| err.to_string())
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Fail { expected: String },
+    Missing,
+}
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Pass => write!(f, "pass"),
+            Verdict::Fail { .. } => write!(f, "FAIL"),
+            Verdict::Missing => write!(f, "missing"),
+        }
+    }
+}
+
+/// Ignores trailing whitespace, which multi-line answers such as day 13's are padded with.
+fn normalize(answer: &str) -> Vec<&str> {
+    answer.trim_end().lines().map(str::trim_end).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::{
+        verify::{Answers, Verdict},
+        Outcome, Part,
+    };
+
+    #[test]
+    fn verdicts() {
+        let answers: Answers =
+            "[day1]\npart1 = \"7\"\npart2 = 5\n\n[day13]\npart2 = '''\n# #\n ##'''"
+                .parse()
+                .unwrap();
+        let verify = |day, part, answer: &str| {
+            answers.verify(&Outcome {
+                day,
+                part,
+                answer: answer.to_string(),
+                parse: Duration::ZERO,
+                solve: Duration::ZERO,
+            })
+        };
+
+        assert_eq!(verify(1, Part::First, "7"), Verdict::Pass);
+        assert_eq!(verify(1, Part::Second, "5"), Verdict::Pass);
+        assert_eq!(
+            verify(1, Part::First, "8"),
+            Verdict::Fail {
+                expected: "7".to_string()
+            }
+        );
+        assert_eq!(verify(13, Part::Second, "# # \n ## \n"), Verdict::Pass);
+        assert_eq!(verify(13, Part::First, "17"), Verdict::Missing);
+        assert_eq!(verify(2, Part::First, "150"), Verdict::Missing);
+        assert!("[day1\npart1 = 7".parse::<Answers>().is_err());
+    }
+}
@@ -0,0 +1,206 @@
+use std::{
+    error::Error,
+    fmt::{Debug, Display},
+    str::FromStr,
+};
+
+/// An error in a puzzle input, pointing at the offending text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// 1-based line number of the offending text
+    pub line: usize,
+    /// 1-based column of the offending text within its line
+    pub column: usize,
+    /// what went wrong, e.g. `Invalid command`
+    pub message: String,
+    /// the offending text, empty if something is missing
+    pub text: String,
+    /// a hint of the expected format
+    pub expected: String,
+}
+impl ParseError {
+    /// Creates an error for `token`, a slice of `line`, on the first line of the input.
+    ///
+    /// If `line` spans several lines the error is on the line of `token` within it, and if
+    /// `token` is not a slice of `line` it is reported at the end of `line`.
+    pub fn new(
+        line: &str,
+        token: &str,
+        message: impl Into<String>,
+        expected: impl Into<String>,
+    ) -> Self {
+        let (line, column) = position_of(line, token);
+        Self {
+            line,
+            column,
+            message: message.into(),
+            text: token.to_string(),
+            expected: expected.into(),
+        }
+    }
+    /// Creates an error for something missing at the end of `line`.
+    pub fn missing(line: &str, message: impl Into<String>, expected: impl Into<String>) -> Self {
+        Self::new(line, &line[line.len()..], message, expected)
+    }
+    /// Creates an error for something missing after the last non-empty line of `input`.
+    pub fn at_end_of(input: &str, message: impl Into<String>, expected: impl Into<String>) -> Self {
+        let (linenum, line) = input
+            .lines()
+            .enumerate()
+            .filter(|(_linenum, line)| !line.trim().is_empty())
+            .last()
+            .unwrap_or((0, ""));
+        Self::missing(line, message, expected).on_line(linenum + 1)
+    }
+    /// Sets the 1-based line number of the error.
+    pub fn on_line(mut self, line: usize) -> Self {
+        self.line = line;
+        self
+    }
+    /// Moves an error found while parsing `part` of `line` so that its column is relative to `line`.
+    pub fn within(mut self, line: &str, part: &str) -> Self {
+        self.column += column_of(line, part) - 1;
+        self
+    }
+}
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )?;
+        if !self.text.is_empty() {
+            write!(f, " `{}`", self.text)?;
+        }
+        write!(f, " (expected {})", self.expected)
+    }
+}
+impl Error for ParseError {}
+
+/// Parses `token`, a slice of `line`, reporting an error at its position if it is invalid.
+pub fn parse_token<T: FromStr>(
+    line: &str,
+    token: &str,
+    message: &str,
+    expected: &str,
+) -> Result<T, ParseError> {
+    token
+        .parse()
+        .map_err(|_| ParseError::new(line, token, message, expected))
+}
+
+/// Parses every non-empty line of `input`, numbering the lines of any error from 1.
+pub fn parse_lines<'a, T>(
+    input: &'a str,
+    mut parse: impl FnMut(&'a str) -> Result<T, ParseError>,
+) -> Result<Vec<T>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_linenum, line)| !line.trim().is_empty())
+        .map(|(linenum, line)| parse(line).map_err(|err| err.on_line(linenum + 1)))
+        .collect()
+}
+
+fn column_of(line: &str, token: &str) -> usize {
+    position_of(line, token).1
+}
+
+/// The 1-based line and column of `token` within `text`.
+fn position_of(text: &str, token: &str) -> (usize, usize) {
+    let start = text.as_ptr() as usize;
+    let offset = (token.as_ptr() as usize).wrapping_sub(start);
+    let offset = if offset <= text.len() && text.is_char_boundary(offset) {
+        offset
+    } else {
+        text.len()
+    };
+
+    let before = &text[..offset];
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    (
+        before.matches('\n').count() + 1,
+        before[line_start..].chars().count() + 1,
+    )
+}
+
+/// The error returned from `main`, whose `Debug` output is the readable error message
+/// instead of the error's structure.
+pub struct Report(Box<dyn Error>);
+impl Report {
+    pub fn from_boxed(err: Box<dyn Error>) -> Self {
+        Self(err)
+    }
+}
+impl Debug for Report {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+impl Display for Report {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+impl<E: Error + 'static> From<E> for Report {
+    fn from(err: E) -> Self {
+        Self(Box::new(err))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{parse_lines, parse_token, ParseError};
+
+    #[test]
+    fn error_position() {
+        let line = "forward five";
+        let err = ParseError::new(line, &line[8..], "Invalid value", "a number");
+        assert_eq!((err.line, err.column), (1, 9));
+        assert_eq!(err.text, "five");
+        assert_eq!(
+            err.on_line(3).to_string(),
+            "line 3, column 9: Invalid value `five` (expected a number)"
+        );
+
+        let err = ParseError::missing(line, "Missing value", "a number");
+        assert_eq!((err.column, err.text.as_str()), (13, ""));
+        assert_eq!(
+            err.to_string(),
+            "line 1, column 13: Missing value (expected a number)"
+        );
+
+        let err = ParseError::at_end_of("start-A\nA-b\n\n", "No end cave", "an `end` cave");
+        assert_eq!((err.line, err.column), (2, 4));
+
+        let input = "3,4\n\n1,x";
+        let err = ParseError::new(input, &input[7..], "Invalid number", "a number");
+        assert_eq!((err.line, err.column), (3, 3));
+    }
+
+    #[test]
+    fn nested_error_position() {
+        let line = "0,9 -> 5,x";
+        let part = &line[7..];
+        let err = parse_token::<i32>(part, &part[2..], "Invalid y", "a number").unwrap_err();
+        assert_eq!(err.column, 3);
+        assert_eq!(err.within(line, part).column, 10);
+    }
+
+    #[test]
+    fn line_numbers() {
+        let input = "1\n\n2\nthree\n";
+        let err = parse_lines(input, |line| {
+            parse_token::<i32>(line, line, "Invalid", "a number")
+        })
+        .unwrap_err();
+        assert_eq!((err.line, err.column), (4, 1));
+        assert_eq!(
+            parse_lines(input.trim_end_matches("three\n"), |line| {
+                parse_token::<i32>(line, line, "Invalid", "a number")
+            }),
+            Ok(vec![1, 2])
+        );
+    }
+}
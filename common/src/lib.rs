@@ -0,0 +1,61 @@
+mod error;
+
+use std::{
+    borrow::Cow,
+    env,
+    error::Error,
+    fmt::Display,
+    fs,
+    io::{self, IsTerminal, Read},
+};
+
+pub use error::{parse_lines, parse_token, ParseError, Report};
+
+pub trait Solver {
+    const DAY: u8;
+    const INPUT: &'static str;
+
+    type Input<'a>;
+    type First: Display;
+    type Second: Display;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError>;
+    fn first(input: &Self::Input<'_>) -> Result<Self::First, Box<dyn Error>>;
+    fn second(input: &Self::Input<'_>) -> Result<Self::Second, Box<dyn Error>>;
+}
+
+pub fn run<S: Solver>() -> Result<(), Report> {
+    let path = env::args().nth(1);
+    let input = read_input(path.as_deref(), S::INPUT)?;
+    let input = S::parse(&input)?;
+
+    println!("{}", S::first(&input).map_err(Report::from_boxed)?);
+    println!("{}", S::second(&input).map_err(Report::from_boxed)?);
+    Ok(())
+}
+
+/// Reads the puzzle input from the file at `path`, or from stdin if `path` is `-`.
+///
+/// Without a path, input piped into stdin is used, and `default` (the bundled
+/// `input.txt`) is only used when stdin is a terminal or empty.
+pub fn read_input(path: Option<&str>, default: &'static str) -> io::Result<Cow<'static, str>> {
+    match path {
+        Some("-") => read_stdin().map(Cow::Owned),
+        Some(path) => fs::read_to_string(path).map(Cow::Owned),
+        None if !io::stdin().is_terminal() => {
+            let input = read_stdin()?;
+            if input.trim().is_empty() {
+                Ok(Cow::Borrowed(default))
+            } else {
+                Ok(Cow::Owned(input))
+            }
+        }
+        None => Ok(Cow::Borrowed(default)),
+    }
+}
+
+fn read_stdin() -> io::Result<String> {
+    let mut input = String::new();
+    io::stdin().read_to_string(&mut input)?;
+    Ok(input)
+}
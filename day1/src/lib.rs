@@ -0,0 +1,388 @@
+use std::{
+    collections::VecDeque,
+    error::Error,
+    io::{self, BufRead},
+    iter::Fuse,
+    num::IntErrorKind,
+};
+
+use common::{ParseError, Solver};
+
+/// [Day 1: Sonar Sweep](https://adventofcode.com/2021/day/1)
+///
+/// ```
+/// let depths = [199, 200, 208, 210, 200, 207, 240, 269, 260, 263];
+/// assert_eq!(day1::first(&depths), 7);
+/// assert_eq!(day1::second(&depths), 5);
+///
+/// // the sums of 3 depths are 607, 618, 618, 617, 647, 716, 769 and 792
+/// let runs: Vec<_> = day1::increasing_runs(depths, 3).collect();
+/// assert_eq!(runs[1], day1::IncreasingRun { start: 3, end: 7, length: 5 });
+/// ```
+pub struct Day1;
+impl Solver for Day1 {
+    const DAY: u8 = 1;
+    const INPUT: &'static str = include_str!("input.txt");
+
+    type Input<'a> = Vec<i32>;
+    type First = usize;
+    type Second = usize;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_depths(input, Mode::Strict).map(|depths| depths.depths)
+    }
+    fn first(input: &Self::Input<'_>) -> Result<Self::First, Box<dyn Error>> {
+        Ok(first(input))
+    }
+    fn second(input: &Self::Input<'_>) -> Result<Self::Second, Box<dyn Error>> {
+        Ok(second(input))
+    }
+}
+
+pub fn first(input: &[i32]) -> usize {
+    count_increases(input, 1)
+}
+
+pub fn second(input: &[i32]) -> usize {
+    count_increases(input, 3)
+}
+
+/// Counts how often the sum of `window` consecutive depths is larger than the previous sum.
+///
+/// Consecutive sums share all but their first and last depth, so only those are compared.
+pub fn count_increases(depths: &[i32], window: usize) -> usize {
+    assert!(window > 0, "window size must be at least 1");
+    depths
+        .windows(window + 1)
+        .filter(|measurements| measurements[window] > measurements[0])
+        .count()
+}
+
+/// The increasing runs of the sums of `window` consecutive depths, read lazily from `depths`.
+pub fn increasing_runs<I: IntoIterator<Item = i32>>(
+    depths: I,
+    window: usize,
+) -> IncreasingRuns<I::IntoIter> {
+    IncreasingRuns {
+        depths: depths.into_iter().fuse(),
+        sweep: SonarSweep::new(window),
+    }
+}
+
+/// Like [`increasing_runs`], but for depths that may fail to be read, such as those of
+/// [`read_depths`], ending with the first error.
+pub fn try_increasing_runs<I: IntoIterator<Item = io::Result<i32>>>(
+    depths: I,
+    window: usize,
+) -> TryIncreasingRuns<I::IntoIter> {
+    TryIncreasingRuns {
+        depths: Some(depths.into_iter()),
+        sweep: SonarSweep::new(window),
+    }
+}
+
+/// How [`parse_depths`] handles invalid lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Mode {
+    /// reject the whole input at its first invalid line
+    #[default]
+    Strict,
+    /// skip invalid lines, collecting their errors
+    Lenient,
+}
+
+/// The depths of a sonar log, and the errors of the invalid lines skipped in lenient mode.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Depths {
+    pub depths: Vec<i32>,
+    pub skipped: Vec<ParseError>,
+}
+
+/// Parses one depth per line, ignoring blank lines and `#` comments.
+pub fn parse_depths(input: &str, mode: Mode) -> Result<Depths, ParseError> {
+    let mut depths = Depths::default();
+    for (linenum, line) in input.lines().enumerate() {
+        match parse_depth(line) {
+            Ok(Some(depth)) => depths.depths.push(depth),
+            Ok(None) => {}
+            Err(err) if mode == Mode::Lenient => depths.skipped.push(err.on_line(linenum + 1)),
+            Err(err) => return Err(err.on_line(linenum + 1)),
+        }
+    }
+    Ok(depths)
+}
+
+/// Reads one depth per line from `reader`, ignoring blank lines and `#` comments.
+///
+/// Invalid depths are reported as [`io::ErrorKind::InvalidData`] errors wrapping a [`ParseError`].
+pub fn read_depths(reader: impl BufRead) -> impl Iterator<Item = io::Result<i32>> {
+    reader.lines().enumerate().filter_map(|(linenum, line)| {
+        let line = match line {
+            Ok(line) => line,
+            Err(err) => return Some(Err(err)),
+        };
+        parse_depth(&line)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err.on_line(linenum + 1)))
+            .transpose()
+    })
+}
+
+/// Parses a line holding a depth, or nothing but a comment.
+fn parse_depth(line: &str) -> Result<Option<i32>, ParseError> {
+    const EXPECTED: &str = "a number such as `199`";
+
+    let depth = line.split('#').next().unwrap_or("").trim();
+    if depth.is_empty() {
+        return Ok(None);
+    }
+
+    match depth.parse::<i32>() {
+        Ok(value) if value < 0 => Err(ParseError::new(line, depth, "Negative depth", EXPECTED)),
+        Ok(value) => Ok(Some(value)),
+        Err(err)
+            if matches!(
+                err.kind(),
+                IntErrorKind::PosOverflow | IntErrorKind::NegOverflow
+            ) =>
+        {
+            Err(ParseError::new(line, depth, "Depth out of range", EXPECTED))
+        }
+        Err(_) => Err(ParseError::new(line, depth, "Invalid depth", EXPECTED)),
+    }
+}
+
+/// A maximal run of window sums each larger than the one before.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IncreasingRun {
+    /// index of the first window of the run, which is also the index of its first depth
+    pub start: usize,
+    /// index of the last window of the run, inclusive
+    pub end: usize,
+    /// number of windows in the run, one more than its number of increases
+    pub length: usize,
+}
+
+/// Compares the sums of `window` consecutive depths as the depths arrive one at a time, keeping
+/// only the last `window` of them.
+#[derive(Debug, Clone)]
+pub struct SonarSweep {
+    window: usize,
+    recent: VecDeque<i32>,
+    depths: usize,
+    increases: usize,
+    run: Option<IncreasingRun>,
+}
+impl SonarSweep {
+    pub fn new(window: usize) -> Self {
+        assert!(window > 0, "window size must be at least 1");
+        Self {
+            window,
+            recent: VecDeque::with_capacity(window),
+            depths: 0,
+            increases: 0,
+            run: None,
+        }
+    }
+    /// Adds the next depth, returning the increasing run it ends, if any.
+    pub fn push(&mut self, depth: i32) -> Option<IncreasingRun> {
+        self.depths += 1;
+        if self.recent.len() < self.window {
+            self.recent.push_back(depth);
+            return None;
+        }
+
+        let oldest = self.recent.pop_front().unwrap();
+        self.recent.push_back(depth);
+        let window = self.depths - self.window;
+        if depth <= oldest {
+            return self.run.take();
+        }
+
+        self.increases += 1;
+        match &mut self.run {
+            Some(run) => {
+                run.end = window;
+                run.length += 1;
+            }
+            None => {
+                self.run = Some(IncreasingRun {
+                    start: window - 1,
+                    end: window,
+                    length: 2,
+                })
+            }
+        }
+        None
+    }
+    /// Ends the sweep, returning the increasing run still going on, if any.
+    pub fn finish(&mut self) -> Option<IncreasingRun> {
+        self.run.take()
+    }
+    pub fn increases(&self) -> usize {
+        self.increases
+    }
+    pub fn depths(&self) -> usize {
+        self.depths
+    }
+}
+
+/// Iterator returned by [`increasing_runs`].
+#[derive(Debug, Clone)]
+pub struct IncreasingRuns<I> {
+    depths: Fuse<I>,
+    sweep: SonarSweep,
+}
+impl<I: Iterator<Item = i32>> Iterator for IncreasingRuns<I> {
+    type Item = IncreasingRun;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for depth in &mut self.depths {
+            if let Some(run) = self.sweep.push(depth) {
+                return Some(run);
+            }
+        }
+        self.sweep.finish()
+    }
+}
+
+/// Iterator returned by [`try_increasing_runs`].
+#[derive(Debug, Clone)]
+pub struct TryIncreasingRuns<I> {
+    /// `None` once the depths end or fail
+    depths: Option<I>,
+    sweep: SonarSweep,
+}
+impl<I: Iterator<Item = io::Result<i32>>> Iterator for TryIncreasingRuns<I> {
+    type Item = io::Result<IncreasingRun>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(depth) = self.depths.as_mut()?.next() {
+            match depth {
+                Ok(depth) => {
+                    if let Some(run) = self.sweep.push(depth) {
+                        return Some(Ok(run));
+                    }
+                }
+                Err(err) => {
+                    self.depths = None;
+                    return Some(Err(err));
+                }
+            }
+        }
+        self.depths = None;
+        self.sweep.finish().map(Ok)
+    }
+}
+
+#[test]
+fn day1_first() {
+    let input = vec![199, 200, 208, 210, 200, 207, 240, 269, 260, 263];
+    assert_eq!(first(&input), 7);
+}
+
+#[test]
+fn day1_second() {
+    let input = vec![199, 200, 208, 210, 200, 207, 240, 269, 260, 263];
+    assert_eq!(second(&input), 5);
+}
+
+#[cfg(test)]
+mod tests {
+    use common::Solver;
+    use proptest::prelude::*;
+
+    use crate::{
+        count_increases, first, increasing_runs, parse_depths, read_depths, second,
+        try_increasing_runs, Day1, IncreasingRun, Mode, SonarSweep,
+    };
+
+    /// Compares the literal sums of each window.
+    fn naive_count(depths: &[i32], window: usize) -> usize {
+        let sums: Vec<i32> = depths
+            .windows(window)
+            .map(|window| window.iter().sum())
+            .collect();
+        sums.windows(2).filter(|sums| sums[1] > sums[0]).count()
+    }
+
+    fn depths() -> impl Strategy<Value = String> {
+        prop::collection::vec(0..10_000i32, 0..50)
+            .prop_map(|depths| depths.iter().map(|depth| format!("{}\n", depth)).collect())
+    }
+
+    #[test]
+    fn day1_parse_modes() {
+        let input = "# depths of 2021-12-01\n199\n\n200 # surfaced\ntwo\n-3\n99999999999\n208\n";
+        let depths = parse_depths(input, Mode::Lenient).unwrap();
+        assert_eq!(depths.depths, [199, 200, 208]);
+        let skipped: Vec<_> = depths
+            .skipped
+            .iter()
+            .map(|err| (err.line, err.message.as_str()))
+            .collect();
+        assert_eq!(
+            skipped,
+            [
+                (5, "Invalid depth"),
+                (6, "Negative depth"),
+                (7, "Depth out of range")
+            ]
+        );
+
+        let err = parse_depths(input, Mode::Strict).unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (5, 1, "two"));
+        assert_eq!(Day1::parse(input).unwrap_err(), err);
+    }
+
+    #[test]
+    fn day1_streaming() {
+        let input = include_str!("test.txt");
+        let runs: Vec<IncreasingRun> = try_increasing_runs(read_depths(input.as_bytes()), 1)
+            .collect::<Result<_, _>>()
+            .unwrap();
+        let run = |start, end| IncreasingRun {
+            start,
+            end,
+            length: end - start + 1,
+        };
+        assert_eq!(runs, [run(0, 3), run(4, 7), run(8, 9)]);
+
+        let mut runs = try_increasing_runs(read_depths("199\n# comment\n2x0\n200\n".as_bytes()), 1);
+        let err = runs.next().unwrap().unwrap_err();
+        assert!(runs.next().is_none());
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+        assert_eq!(
+            err.to_string(),
+            "line 3, column 1: Invalid depth `2x0` (expected a number such as `199`)"
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn day1_matches_naive(input in depths()) {
+            let depths = Day1::parse(&input).unwrap();
+            prop_assert_eq!(second(&depths), naive_count(&depths, 3));
+            prop_assert_eq!(
+                first(&depths),
+                depths.iter().zip(depths.iter().skip(1)).filter(|(a, b)| b > a).count()
+            );
+        }
+
+        #[test]
+        fn day1_sweep_matches_naive(input in depths(), window in 1..6usize) {
+            let depths = Day1::parse(&input).unwrap();
+            let count = naive_count(&depths, window);
+            prop_assert_eq!(count_increases(&depths, window), count);
+
+            let mut sweep = SonarSweep::new(window);
+            let mut runs = Vec::new();
+            for depth in &depths {
+                runs.extend(sweep.push(*depth));
+            }
+            runs.extend(sweep.finish());
+            prop_assert_eq!(sweep.increases(), count);
+            prop_assert_eq!(runs.iter().map(|run| run.length - 1).sum::<usize>(), count);
+            prop_assert_eq!(increasing_runs(depths.iter().copied(), window).collect::<Vec<_>>(), runs);
+        }
+    }
+}
@@ -1,41 +1,36 @@
-fn main() {
-    let input = include_str!("input.txt");
-    let input = input
-        .lines()
-        .enumerate()
-        .filter(|(_linenum, line)| !line.is_empty())
-        .map(|(linenum, line)| match line.parse::<i32>() {
-            Ok(num) => num,
-            Err(err) => panic!("Got {:?} error at line {} of input.txt", err, linenum),
-        })
-        .collect::<Vec<i32>>();
-
-    println!("{}", first(&input));
-    println!("{}", second(&input));
-}
+use std::{env, process};
 
-fn first(input: &[i32]) -> usize {
-    input
-        .windows(2)
-        .filter(|measurements| measurements[1] > measurements[0])
-        .count()
-}
+use common::Solver;
+use day1::{Day1, Depths, Mode};
 
-fn second(input: &[i32]) -> usize {
-    input
-        .windows(4)
-        .filter(|measurements| measurements[3] > measurements[0]) // (B+C+D)-(A+B+C) = D-A
-        .count()
-}
+const USAGE: &str = "Usage: day1 [--lenient] [input]
+
+--lenient    skip invalid depths with a warning instead of rejecting the input";
+
+fn main() -> Result<(), common::Report> {
+    let mut mode = Mode::Strict;
+    let mut path = None;
+
+    for arg in env::args().skip(1) {
+        match arg.as_str() {
+            "--lenient" => mode = Mode::Lenient,
+            _ if path.is_none() => path = Some(arg),
+            _ => usage(&format!("Unexpected argument: {}", arg)),
+        }
+    }
+
+    let input = common::read_input(path.as_deref(), Day1::INPUT)?;
+    let Depths { depths, skipped } = day1::parse_depths(&input, mode)?;
+    for err in skipped {
+        eprintln!("Skipped {}", err);
+    }
 
-#[test]
-fn day1_first() {
-    let input = vec![199, 200, 208, 210, 200, 207, 240, 269, 260, 263];
-    assert_eq!(first(&input), 7);
+    println!("{}", day1::first(&depths));
+    println!("{}", day1::second(&depths));
+    Ok(())
 }
 
-#[test]
-fn day1_second() {
-    let input = vec![199, 200, 208, 210, 200, 207, 240, 269, 260, 263];
-    assert_eq!(second(&input), 5);
+fn usage(err: &str) -> ! {
+    eprintln!("{}\n{}", err, USAGE);
+    process::exit(2);
 }
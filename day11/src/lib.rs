@@ -0,0 +1,116 @@
+use std::{error::Error, str::FromStr};
+
+use common::{ParseError, Solver};
+use grid::{Grid, Point};
+
+/// [Day 11: Dumbo Octopus](https://adventofcode.com/2021/day/11)
+///
+/// ```
+/// let octopuses: day11::Octupuses = include_str!("test.txt").parse().unwrap();
+/// assert_eq!(day11::first(octopuses.clone()), 1656);
+/// assert_eq!(day11::second(octopuses), 195);
+/// ```
+pub struct Day11;
+impl Solver for Day11 {
+    const DAY: u8 = 11;
+    const INPUT: &'static str = include_str!("input.txt");
+
+    type Input<'a> = Octupuses;
+    type First = i32;
+    type Second = i32;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        input.parse()
+    }
+    fn first(input: &Self::Input<'_>) -> Result<Self::First, Box<dyn Error>> {
+        Ok(first(input.clone()))
+    }
+    fn second(input: &Self::Input<'_>) -> Result<Self::Second, Box<dyn Error>> {
+        Ok(second(input.clone()))
+    }
+}
+
+pub fn first(mut octopuses: Octupuses) -> i32 {
+    const STEPS: i32 = 100;
+    let mut flash_count = 0;
+
+    for _ in 0..STEPS {
+        flash_count += octopuses.count_flashes();
+    }
+
+    flash_count
+}
+
+pub fn second(mut octopuses: Octupuses) -> i32 {
+    for steps in 1.. {
+        octopuses.count_flashes();
+        if octopuses.0.iter().all(|octupus| *octupus == 0) {
+            return steps;
+        }
+    }
+
+    panic!("the octopuses never all flashed at the same time")
+}
+
+#[derive(Debug, Clone)]
+pub struct Octupuses(Grid<u8>);
+impl Octupuses {
+    pub fn count_flashes(&mut self) -> i32 {
+        let mut flashed = Grid::new(self.0.rows(), self.0.cols(), false);
+        for octopus in self.0.iter_mut() {
+            *octopus += 1;
+        }
+
+        loop {
+            let flashers: Vec<Point> = self.0.points().filter(|point| self.0[*point] > 9).collect();
+
+            if flashers.is_empty() {
+                break;
+            }
+            for point in &flashers {
+                self.0[*point] = 0;
+                flashed[*point] = true;
+            }
+            for point in flashers {
+                for neighbour in self.0.all_neighbours(point) {
+                    if !flashed[neighbour] {
+                        self.0[neighbour] += 1;
+                    }
+                }
+            }
+        }
+
+        flashed
+            .iter()
+            .filter(|octopus_flashed| **octopus_flashed)
+            .count() as i32
+    }
+}
+impl FromStr for Octupuses {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self(s.parse()?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{first, second, Octupuses};
+
+    fn load_test_data() -> Octupuses {
+        include_str!("test.txt").parse().unwrap()
+    }
+
+    #[test]
+    fn day11_first() {
+        let input = load_test_data();
+        assert_eq!(first(input), 1656);
+    }
+
+    #[test]
+    fn day11_second() {
+        let input = load_test_data();
+        assert_eq!(second(input), 195);
+    }
+}
@@ -0,0 +1,261 @@
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    error::Error,
+    str::FromStr,
+};
+
+use common::{parse_lines, ParseError, Solver};
+
+/// [Day 12: Passage Pathing](https://adventofcode.com/2021/day/12)
+///
+/// ```
+/// let caves: day12::CaveSystem = "start-A\nstart-b\nA-c\nA-b\nb-d\nA-end\nb-end".parse().unwrap();
+/// assert_eq!(day12::first(&caves), 10);
+/// assert_eq!(day12::second(&caves), 36);
+/// ```
+pub struct Day12;
+impl Solver for Day12 {
+    const DAY: u8 = 12;
+    const INPUT: &'static str = include_str!("input.txt");
+
+    type Input<'a> = CaveSystem;
+    type First = i32;
+    type Second = i32;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        input.parse()
+    }
+    fn first(input: &Self::Input<'_>) -> Result<Self::First, Box<dyn Error>> {
+        Ok(first(input))
+    }
+    fn second(input: &Self::Input<'_>) -> Result<Self::Second, Box<dyn Error>> {
+        Ok(second(input))
+    }
+}
+
+pub fn first(caves: &CaveSystem) -> i32 {
+    let mut paths = 0;
+    let mut possible_paths = VecDeque::new();
+    possible_paths.push_back((0, vec![false; caves.caves.len()]));
+    let end = caves.caves.len() - 1;
+
+    while let Some((current, visited)) = possible_paths.pop_front() {
+        if current == end {
+            paths += 1;
+            continue;
+        }
+        for i in
+            caves.connections[current]
+                .iter()
+                .enumerate()
+                .filter_map(|(index, is_connected)| {
+                    if *is_connected
+                        && match caves.caves[index] {
+                            Node::Start => false,
+                            Node::Small(_) => !visited[index],
+                            Node::Big(_) | Node::End => true,
+                        }
+                    {
+                        Some(index)
+                    } else {
+                        None
+                    }
+                })
+        {
+            let mut visited = visited.clone();
+            visited[i] = true;
+            possible_paths.push_back((i, visited));
+        }
+    }
+
+    paths
+}
+
+pub fn second(caves: &CaveSystem) -> i32 {
+    let mut paths = 0;
+    let mut possible_paths = VecDeque::new();
+    possible_paths.push_back((0, vec![0; caves.caves.len()]));
+    let end = caves.caves.len() - 1;
+
+    while let Some((current, visited)) = possible_paths.pop_front() {
+        if current == end {
+            paths += 1;
+            continue;
+        }
+        let can_visit_small_caves_twice =
+            visited
+                .iter()
+                .zip(caves.caves.iter())
+                .all(|(times_visited, node)| {
+                    if let Node::Small(_) = node {
+                        *times_visited < 2
+                    } else {
+                        true
+                    }
+                });
+
+        for i in
+            caves.connections[current]
+                .iter()
+                .enumerate()
+                .filter_map(|(index, is_connected)| {
+                    if *is_connected
+                        && match caves.caves[index] {
+                            Node::Start => false,
+                            Node::Small(_) => {
+                                visited[index] == 0
+                                    || (visited[index] == 1 && can_visit_small_caves_twice)
+                            }
+                            Node::Big(_) | Node::End => true,
+                        }
+                    {
+                        Some(index)
+                    } else {
+                        None
+                    }
+                })
+        {
+            let mut visited = visited.clone();
+            visited[i] += 1;
+            possible_paths.push_back((i, visited));
+        }
+    }
+
+    paths
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Node {
+    Start,
+    Small(String),
+    Big(String),
+    End,
+}
+impl FromStr for Node {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "start" => Ok(Self::Start),
+            "end" => Ok(Self::End),
+            name if !name.is_empty() && name.chars().all(|chr| chr.is_ascii_uppercase()) => {
+                Ok(Self::Big(name.to_string()))
+            }
+            name if !name.is_empty() && name.chars().all(|chr| chr.is_ascii_lowercase()) => {
+                Ok(Self::Small(name.to_string()))
+            }
+            name => Err(ParseError::new(
+                s,
+                name,
+                "Invalid cave",
+                "a cave name of only uppercase or only lowercase letters",
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CaveSystem {
+    caves: Vec<Node>,
+    connections: Vec<Vec<bool>>,
+}
+impl FromStr for CaveSystem {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        const EXPECTED: &str = "connections between two caves such as `start-A`";
+
+        let edges: Vec<(Node, Node)> = parse_lines(s, |line| {
+            let (first, second) = line
+                .split_once('-')
+                .ok_or_else(|| ParseError::new(line, line.trim(), "Missing `-`", EXPECTED))?;
+            let first = first
+                .parse()
+                .map_err(|err: ParseError| err.within(line, first))?;
+            let second = second
+                .parse()
+                .map_err(|err: ParseError| err.within(line, second))?;
+            Ok((first, second))
+        })?;
+
+        let mut nodes: HashSet<Node> = HashSet::new();
+        for (first, second) in &edges {
+            nodes.insert(first.clone());
+            nodes.insert(second.clone());
+        }
+
+        let mut nodes: Vec<Node> = nodes.into_iter().collect();
+        let start = nodes
+            .iter()
+            .position(|node| matches!(node, Node::Start))
+            .ok_or_else(|| ParseError::at_end_of(s, "No start node", EXPECTED))?;
+        nodes.swap(start, 0);
+        let end = nodes
+            .iter()
+            .position(|node| matches!(node, Node::End))
+            .ok_or_else(|| ParseError::at_end_of(s, "No end node", EXPECTED))?;
+        let length = nodes.len();
+        nodes.swap(end, length - 1);
+
+        let mappings: HashMap<&Node, usize> = nodes.iter().zip(0..).collect();
+
+        let mut connections = vec![vec![false; nodes.len()]; nodes.len()];
+        for (first, second) in &edges {
+            let first = mappings[first];
+            let second = mappings[second];
+
+            connections[first][second] = true;
+            connections[second][first] = true;
+        }
+
+        Ok(Self {
+            caves: nodes,
+            connections,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{first, second, CaveSystem};
+
+    fn load_test_data_1() -> CaveSystem {
+        include_str!("test1.txt").parse().unwrap()
+    }
+    fn load_test_data_2() -> CaveSystem {
+        include_str!("test2.txt").parse().unwrap()
+    }
+    fn load_test_data_3() -> CaveSystem {
+        include_str!("test3.txt").parse().unwrap()
+    }
+
+    #[test]
+    fn day12_first() {
+        let input = load_test_data_1();
+        assert_eq!(first(&input), 10);
+        let input = load_test_data_2();
+        assert_eq!(first(&input), 19);
+        let input = load_test_data_3();
+        assert_eq!(first(&input), 226);
+    }
+
+    #[test]
+    fn day12_parse_errors() {
+        let err = "start-A\nA_b\nA-end".parse::<CaveSystem>().unwrap_err();
+        assert_eq!((err.line, err.message.as_str()), (2, "Missing `-`"));
+        let err = "start-A\nA-bC\nA-end".parse::<CaveSystem>().unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (2, 3, "bC"));
+        let err = "start-A\nA-b\n".parse::<CaveSystem>().unwrap_err();
+        assert_eq!((err.line, err.message.as_str()), (2, "No end node"));
+    }
+
+    #[test]
+    fn day12_second() {
+        let input = load_test_data_1();
+        assert_eq!(second(&input), 36);
+        let input = load_test_data_2();
+        assert_eq!(second(&input), 103);
+        let input = load_test_data_3();
+        assert_eq!(second(&input), 3509);
+    }
+}
@@ -0,0 +1,304 @@
+use std::{error::Error, fmt::Display, str::FromStr};
+
+use common::{parse_token, ParseError, Solver};
+use grid::{Grid, Point};
+
+/// [Day 13: Transparent Origami](https://adventofcode.com/2021/day/13)
+///
+/// ```
+/// let paper: day13::PaperFolds = include_str!("test.txt").parse().unwrap();
+/// assert_eq!(day13::first(paper.clone()), Ok(17));
+/// print!("{}", day13::second(paper).unwrap());
+/// ```
+pub struct Day13;
+impl Solver for Day13 {
+    const DAY: u8 = 13;
+    const INPUT: &'static str = include_str!("input.txt");
+
+    type Input<'a> = PaperFolds;
+    type First = i32;
+    type Second = Paper;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        input.parse()
+    }
+    fn first(input: &Self::Input<'_>) -> Result<Self::First, Box<dyn Error>> {
+        Ok(first(input.clone())?)
+    }
+    fn second(input: &Self::Input<'_>) -> Result<Self::Second, Box<dyn Error>> {
+        Ok(second(input.clone())?)
+    }
+}
+
+pub fn first(mut paper: PaperFolds) -> Result<i32, FoldError> {
+    paper.fold_n(1)?;
+    Ok(paper.count_dots())
+}
+
+pub fn second(mut paper: PaperFolds) -> Result<Paper, FoldError> {
+    paper.fold()?;
+    Ok(paper.paper)
+}
+
+#[derive(Debug, Clone)]
+pub struct Paper(Grid<bool>);
+impl Paper {
+    pub fn from_dots(dots: &[(usize, usize)]) -> Self {
+        let max_x = dots.iter().map(|(x, _y)| x).max().copied().unwrap_or(0);
+        let max_y = dots.iter().map(|(_x, y)| y).max().copied().unwrap_or(0);
+
+        let mut paper = Grid::new(max_y + 1, max_x + 1, false);
+        for (x, y) in dots {
+            paper[Point::new(*y, *x)] = true;
+        }
+
+        Self(paper)
+    }
+    pub fn count_dots(&self) -> i32 {
+        self.0.iter().filter(|dot| **dot).count() as i32
+    }
+    /// Folds the paper, unless the fold line is not on the second half of the paper.
+    pub fn fold(&mut self, fold: &Fold) -> Result<(), FoldError> {
+        let (line, size) = match fold {
+            Fold::FoldAtX(column) => (*column, self.0.cols()),
+            Fold::FoldAtY(row) => (*row, self.0.rows()),
+        };
+        if line < size / 2 || line >= size {
+            let fold = fold.clone();
+            return Err(FoldError { fold, size });
+        }
+
+        match fold {
+            Fold::FoldAtX(i) => self.fold_x(*i),
+            Fold::FoldAtY(i) => self.fold_y(*i),
+        }
+        Ok(())
+    }
+    fn fold_x(&mut self, column: usize) {
+        self.0 = Grid::from_fn(self.0.rows(), column, |point| {
+            let mirrored = Point::new(point.row, 2 * column - point.col);
+            self.0[point] || self.0.get(mirrored).copied().unwrap_or(false)
+        });
+    }
+    fn fold_y(&mut self, row: usize) {
+        self.0 = Grid::from_fn(row, self.0.cols(), |point| {
+            let mirrored = Point::new(2 * row - point.row, point.col);
+            self.0[point] || self.0.get(mirrored).copied().unwrap_or(false)
+        });
+    }
+}
+impl Display for Paper {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.0.row_slices() {
+            for dot in row {
+                if *dot {
+                    write!(f, "#")?;
+                } else {
+                    write!(f, " ")?;
+                }
+            }
+            writeln!(f,)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Fold {
+    FoldAtX(usize),
+    FoldAtY(usize),
+}
+impl Display for Fold {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Fold::FoldAtX(column) => write!(f, "fold along x={}", column),
+            Fold::FoldAtY(row) => write!(f, "fold along y={}", row),
+        }
+    }
+}
+
+/// A fold whose line is not on the second half of the paper, `size` wide or high across it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FoldError {
+    pub fold: Fold,
+    pub size: usize,
+}
+impl Display for FoldError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let across = match self.fold {
+            Fold::FoldAtX(_) => "wide",
+            Fold::FoldAtY(_) => "high",
+        };
+        write!(
+            f,
+            "`{}` is not on the second half of the paper, {} {}",
+            self.fold, self.size, across
+        )
+    }
+}
+impl Error for FoldError {}
+impl FromStr for Fold {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        const EXPECTED: &str = "`fold along <axis>=<number>`";
+
+        let fold = s
+            .trim()
+            .strip_prefix("fold along")
+            .ok_or_else(|| ParseError::new(s, s.trim(), "Invalid format", EXPECTED))?
+            .trim();
+        let (axis, position) = fold
+            .split_once('=')
+            .ok_or_else(|| ParseError::missing(s, "Missing `=`", EXPECTED))?;
+        let fold: fn(usize) -> Fold = match axis {
+            "x" => Fold::FoldAtX,
+            "y" => Fold::FoldAtY,
+            _ => return Err(ParseError::new(s, axis, "Invalid axis", EXPECTED)),
+        };
+
+        Ok(fold(parse_token(s, position, "Invalid number", EXPECTED)?))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PaperFolds {
+    paper: Paper,
+    folds: Vec<Fold>,
+}
+impl PaperFolds {
+    /// Makes the first `n` folds left, or as many as are left.
+    pub fn fold_n(&mut self, n: usize) -> Result<(), FoldError> {
+        let n = n.min(self.folds.len());
+        for fold in &self.folds[..n] {
+            self.paper.fold(fold)?;
+        }
+        self.folds = self.folds[n..].to_vec();
+        Ok(())
+    }
+    pub fn fold(&mut self) -> Result<(), FoldError> {
+        self.fold_n(self.folds.len())
+    }
+    pub fn count_dots(&self) -> i32 {
+        self.paper.count_dots()
+    }
+}
+impl FromStr for PaperFolds {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        fn parse_dot(line: &str) -> Result<(usize, usize), ParseError> {
+            const EXPECTED: &str = "a dot such as `6,10`";
+            // the paper is stored densely, so a single far away dot would need a huge paper
+            const MAX_COORDINATE: usize = 10_000;
+
+            let coordinate = |token: &str, message: &str| {
+                let coordinate: usize = parse_token(line, token, message, EXPECTED)?;
+                if coordinate > MAX_COORDINATE {
+                    let message = format!("Coordinate larger than {}", MAX_COORDINATE);
+                    return Err(ParseError::new(line, token, message, EXPECTED));
+                }
+                Ok(coordinate)
+            };
+
+            let (x, y) = line
+                .trim()
+                .split_once(',')
+                .ok_or_else(|| ParseError::new(line, line.trim(), "Missing `,`", EXPECTED))?;
+            Ok((
+                coordinate(x, "Invalid x coordinate")?,
+                coordinate(y, "Invalid y coordinate")?,
+            ))
+        }
+
+        let mut lines = s.lines().enumerate();
+        let mut dots: Vec<(usize, usize)> = Vec::new();
+
+        for (linenum, line) in &mut lines {
+            if line.trim().is_empty() {
+                break;
+            }
+            dots.push(parse_dot(line).map_err(|err| err.on_line(linenum + 1))?);
+        }
+
+        let folds = lines
+            .filter(|(_linenum, line)| !line.trim().is_empty())
+            .map(|(linenum, line)| {
+                line.parse()
+                    .map_err(|err: ParseError| err.on_line(linenum + 1))
+            })
+            .collect::<Result<Vec<Fold>, _>>()?;
+        if folds.is_empty() {
+            return Err(ParseError::at_end_of(
+                s,
+                "Missing folds",
+                "`fold along <axis>=<number>`",
+            ));
+        }
+
+        let paper = Paper::from_dots(&dots);
+        Ok(Self { paper, folds })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{first, second, Fold, FoldError, PaperFolds};
+
+    fn load_test_data() -> PaperFolds {
+        include_str!("test.txt").parse().unwrap()
+    }
+
+    #[test]
+    fn day13_first() {
+        let input = load_test_data();
+        assert_eq!(first(input), Ok(17));
+    }
+
+    #[test]
+    fn day13_fold_errors() {
+        let input: PaperFolds = "0,0\n2,0\n\nfold along x=5".parse().unwrap();
+        let err = first(input).unwrap_err();
+        assert_eq!(
+            err,
+            FoldError {
+                fold: Fold::FoldAtX(5),
+                size: 3
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "`fold along x=5` is not on the second half of the paper, 3 wide"
+        );
+        let input: PaperFolds = "0,0\n0,4\n\nfold along y=2\nfold along y=0"
+            .parse()
+            .unwrap();
+        assert_eq!(second(input).unwrap_err().fold, Fold::FoldAtY(0));
+    }
+
+    #[test]
+    fn day13_parse_errors() {
+        let err = "6,10\n0,x\n\nfold along y=7"
+            .parse::<PaperFolds>()
+            .unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (2, 3, "x"));
+        let err = "6,10\n0;2\n\nfold along y=7"
+            .parse::<PaperFolds>()
+            .unwrap_err();
+        assert_eq!((err.line, err.message.as_str()), (2, "Missing `,`"));
+        let err = "6,10\n\nfold along z=7".parse::<PaperFolds>().unwrap_err();
+        assert_eq!(
+            (err.line, err.column, err.message.as_str()),
+            (3, 12, "Invalid axis")
+        );
+        let err = "6,10\n0,100000000000\n\nfold along y=7"
+            .parse::<PaperFolds>()
+            .unwrap_err();
+        assert_eq!(
+            (err.line, err.message.as_str()),
+            (2, "Coordinate larger than 10000")
+        );
+        let err = "6,10\n\n".parse::<PaperFolds>().unwrap_err();
+        assert_eq!(err.message, "Missing folds");
+    }
+}
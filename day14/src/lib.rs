@@ -0,0 +1,273 @@
+use std::{collections::HashMap, error::Error, str::FromStr};
+
+use common::{ParseError, Solver};
+
+/// [Day 14: Extended Polymerization](https://adventofcode.com/2021/day/14)
+///
+/// ```
+/// let polymer: day14::Polymer = include_str!("test.txt").parse().unwrap();
+/// assert_eq!(day14::first(polymer.clone()), 1588);
+/// assert_eq!(day14::second(polymer), 2_188_189_693_529);
+/// ```
+pub struct Day14;
+impl Solver for Day14 {
+    const DAY: u8 = 14;
+    const INPUT: &'static str = include_str!("input.txt");
+
+    type Input<'a> = Polymer;
+    type First = i64;
+    type Second = i64;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        input.parse()
+    }
+    fn first(input: &Self::Input<'_>) -> Result<Self::First, Box<dyn Error>> {
+        Ok(first(input.clone()))
+    }
+    fn second(input: &Self::Input<'_>) -> Result<Self::Second, Box<dyn Error>> {
+        Ok(second(input.clone()))
+    }
+}
+
+pub fn first(mut polymer: Polymer) -> i64 {
+    const STEPS: i32 = 10;
+
+    for _ in 0..STEPS {
+        polymer.extend_polymer();
+    }
+
+    let counts = polymer.element_counts();
+
+    let mut counts = counts.into_values().collect::<Vec<_>>();
+    counts.sort_unstable_by_key(|count| *count);
+
+    counts.last().unwrap() - counts.first().unwrap()
+}
+
+pub fn second(mut polymer: Polymer) -> i64 {
+    const STEPS: i32 = 40;
+
+    for _ in 0..STEPS {
+        polymer.extend_polymer();
+    }
+
+    let counts = polymer.element_counts();
+
+    let mut counts = counts.into_values().collect::<Vec<_>>();
+    counts.sort_unstable_by_key(|count| *count);
+
+    counts.last().unwrap() - counts.first().unwrap()
+}
+
+#[derive(Debug, Clone)]
+pub struct Polymer {
+    sequences: HashMap<Vec<char>, i64>,
+    insertion_rules: HashMap<[char; 2], [[char; 2]; 2]>,
+    // the first and last character of input will remain the first or last because
+    // chracters are added to the middle of a sequence
+    first: char,
+    last: char,
+}
+impl Polymer {
+    pub fn extend_polymer(&mut self) {
+        let mut new_seq = HashMap::with_capacity(self.sequences.len() * 2);
+
+        for (seq, count) in &self.sequences {
+            if self.insertion_rules.contains_key(seq.as_slice()) {
+                for output in self.insertion_rules[seq.as_slice()] {
+                    *new_seq.entry(output.to_vec()).or_insert(0) += count;
+                }
+            } else {
+                *new_seq.entry(seq.clone()).or_insert(0) += count;
+            }
+        }
+
+        self.sequences = new_seq;
+    }
+    pub fn element_counts(&self) -> HashMap<char, i64> {
+        let mut counts = HashMap::new();
+        for (seq, count) in &self.sequences {
+            for chr in seq.iter() {
+                *counts.entry(*chr).or_insert(0) += count;
+            }
+        }
+        *counts.entry(self.first).or_insert(0) += 1;
+        *counts.entry(self.last).or_insert(0) += 1;
+
+        for count in counts.values_mut() {
+            *count /= 2;
+        }
+
+        counts
+    }
+}
+impl FromStr for Polymer {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        const TEMPLATE: &str = "a polymer template of uppercase letters such as `NNCB`";
+        const RULE: &str = "a pair insertion rule such as `CH -> B`";
+
+        fn elements<const N: usize>(
+            line: &str,
+            s: &str,
+            expected: &str,
+        ) -> Result<[char; N], ParseError> {
+            let elements: Vec<char> = s.trim().chars().collect();
+            if elements.len() != N || !elements.iter().all(char::is_ascii_uppercase) {
+                return Err(ParseError::new(
+                    line,
+                    s.trim(),
+                    "Invalid elements",
+                    expected,
+                ));
+            }
+            Ok(elements.try_into().unwrap())
+        }
+
+        let mut s = s.lines().enumerate();
+        let mut start = None;
+
+        for (linenum, line) in &mut s {
+            if line.trim().is_empty() {
+                break;
+            }
+            start = Some((linenum, line));
+        }
+        let (linenum, line) =
+            start.ok_or_else(|| ParseError::missing("", "Missing polymer template", TEMPLATE))?;
+        let template = line.trim();
+        if let Some((i, element)) = template
+            .char_indices()
+            .find(|(_i, element)| !element.is_ascii_uppercase())
+        {
+            let element = &template[i..i + element.len_utf8()];
+            return Err(
+                ParseError::new(line, element, "Invalid element", TEMPLATE).on_line(linenum + 1)
+            );
+        }
+        let start: Vec<char> = template.chars().collect();
+        let mut sequences = HashMap::new();
+        for window in start.windows(2) {
+            let window = window.to_vec();
+            *sequences.entry(window).or_insert(0) += 1;
+        }
+
+        let insertion_rules = s
+            .filter(|(_linenum, line)| !line.trim().is_empty())
+            .map(|(linenum, line)| {
+                let rule = || {
+                    let (input, output) = line
+                        .split_once("->")
+                        .ok_or_else(|| ParseError::missing(line, "Missing `->`", RULE))?;
+                    let [first, second] = elements(line, input, RULE)?;
+                    let [output] = elements(line, output, RULE)?;
+                    Ok(([first, second], [[first, output], [output, second]]))
+                };
+                rule().map_err(|err: ParseError| err.on_line(linenum + 1))
+            })
+            .collect::<Result<_, ParseError>>()?;
+
+        Ok(Self {
+            sequences,
+            insertion_rules,
+            first: *start.first().unwrap(),
+            last: *start.last().unwrap(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use proptest::prelude::*;
+
+    use crate::{first, second, Polymer};
+
+    const ELEMENTS: [char; 4] = ['B', 'C', 'H', 'N'];
+
+    /// Inserts the elements into the polymer string itself, one step at a time.
+    fn naive_counts(
+        template: &str,
+        rules: &HashMap<(char, char), char>,
+        steps: usize,
+    ) -> HashMap<char, i64> {
+        let mut polymer: Vec<char> = template.chars().collect();
+        for _ in 0..steps {
+            let mut next = vec![polymer[0]];
+            for pair in polymer.windows(2) {
+                if let Some(inserted) = rules.get(&(pair[0], pair[1])) {
+                    next.push(*inserted);
+                }
+                next.push(pair[1]);
+            }
+            polymer = next;
+        }
+
+        let mut counts = HashMap::new();
+        for element in polymer {
+            *counts.entry(element).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    fn polymers() -> impl Strategy<Value = (String, HashMap<(char, char), char>)> {
+        let element = prop::sample::select(&ELEMENTS[..]);
+        let template = prop::collection::vec(element.clone(), 1..6)
+            .prop_map(|elements| elements.into_iter().collect::<String>());
+        let pairs: Vec<(char, char)> = ELEMENTS
+            .iter()
+            .flat_map(|first| ELEMENTS.iter().map(move |second| (*first, *second)))
+            .collect();
+        let rules = prop::collection::vec(prop::option::of(element), pairs.len()).prop_map(
+            move |inserted| {
+                pairs
+                    .iter()
+                    .zip(inserted)
+                    .filter_map(|(pair, inserted)| Some((*pair, inserted?)))
+                    .collect()
+            },
+        );
+        (template, rules)
+    }
+
+    fn load_test_data() -> Polymer {
+        include_str!("test.txt").parse().unwrap()
+    }
+
+    #[test]
+    fn day14_first() {
+        let input = load_test_data();
+        assert_eq!(first(input), 1588);
+    }
+    #[test]
+    fn day14_parse_errors() {
+        let err = "NNcB\n\nCH -> B".parse::<Polymer>().unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (1, 3, "c"));
+        let err = "NNCB\n\nCH -> B\nHH => N".parse::<Polymer>().unwrap_err();
+        assert_eq!((err.line, err.message.as_str()), (4, "Missing `->`"));
+        let err = "NNCB\n\nCH -> B\nHHH -> N".parse::<Polymer>().unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (4, 1, "HHH"));
+    }
+
+    #[test]
+    fn day14_second() {
+        let input = load_test_data();
+        assert_eq!(second(input), 2_188_189_693_529);
+    }
+
+    proptest! {
+        #[test]
+        fn day14_matches_naive((template, rules) in polymers(), steps in 0..=10usize) {
+            let input = rules.iter().fold(format!("{}\n\n", template), |input, ((a, b), c)| {
+                input + &format!("{}{} -> {}\n", a, b, c)
+            });
+            let mut polymer: Polymer = input.parse().unwrap();
+            for _ in 0..steps {
+                polymer.extend_polymer();
+            }
+            prop_assert_eq!(polymer.element_counts(), naive_counts(&template, &rules, steps));
+        }
+    }
+}
@@ -0,0 +1,164 @@
+use std::{collections::BinaryHeap, error::Error, str::FromStr};
+
+use common::{ParseError, Solver};
+use grid::{Grid, Point};
+
+/// [Day 15: Chiton](https://adventofcode.com/2021/day/15)
+///
+/// ```
+/// let cavern: day15::Cavern = include_str!("test.txt").parse().unwrap();
+/// assert_eq!(day15::first(cavern.clone()), 40);
+/// assert_eq!(day15::second(cavern), 315);
+/// ```
+pub struct Day15;
+impl Solver for Day15 {
+    const DAY: u8 = 15;
+    const INPUT: &'static str = include_str!("input.txt");
+
+    type Input<'a> = Cavern;
+    type First = i32;
+    type Second = i32;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        input.parse()
+    }
+    fn first(input: &Self::Input<'_>) -> Result<Self::First, Box<dyn Error>> {
+        Ok(first(input.clone()))
+    }
+    fn second(input: &Self::Input<'_>) -> Result<Self::Second, Box<dyn Error>> {
+        Ok(second(input.clone()))
+    }
+}
+
+pub fn first(cavern: Cavern) -> i32 {
+    lowest_risk_to_end(&cavern)
+}
+
+pub fn second(mut cavern: Cavern) -> i32 {
+    cavern.expand(5);
+
+    lowest_risk_to_end(&cavern)
+}
+
+pub fn lowest_risk_to_end(cavern: &Cavern) -> i32 {
+    let mut current = Point::new(0, 0);
+    let dst = Point::new(cavern.0.rows() - 1, cavern.0.cols() - 1);
+
+    let mut lowest_risk = Grid::new(
+        cavern.0.rows(),
+        cavern.0.cols(),
+        Status::Unvisited(i32::MAX),
+    );
+    lowest_risk[current] = Status::Visited(0);
+    let mut queue: BinaryHeap<NavigationState> = BinaryHeap::new();
+
+    while current != dst {
+        let current_dist = lowest_risk[current].distance();
+
+        for neighbour in cavern.0.neighbours(current) {
+            if !lowest_risk[neighbour].is_unvisited() {
+                continue;
+            }
+
+            let distance = current_dist + i32::from(cavern.0[neighbour]);
+            if distance < lowest_risk[neighbour].distance() {
+                lowest_risk[neighbour] = Status::Unvisited(distance);
+            }
+            queue.push(NavigationState {
+                distance,
+                coordinates: neighbour,
+            });
+        }
+
+        lowest_risk[current] = Status::Visited(current_dist);
+        loop {
+            let state = queue.pop().unwrap();
+            if lowest_risk[state.coordinates].is_unvisited() {
+                current = state.coordinates;
+                break;
+            }
+        }
+    }
+
+    lowest_risk[dst].distance()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum Status {
+    Visited(i32),
+    Unvisited(i32),
+}
+impl Status {
+    fn distance(&self) -> i32 {
+        match self {
+            Status::Visited(i) | Status::Unvisited(i) => *i,
+        }
+    }
+    fn is_unvisited(&self) -> bool {
+        matches!(self, Status::Unvisited(_))
+    }
+}
+
+#[derive(Debug, Hash, PartialEq, Eq)]
+struct NavigationState {
+    distance: i32,
+    coordinates: Point,
+}
+impl PartialOrd for NavigationState {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl Ord for NavigationState {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.distance.cmp(&other.distance).reverse()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Cavern(Grid<u8>);
+impl Cavern {
+    pub fn expand(&mut self, ratio: usize) {
+        // repeats the cavern `ratio` times in both directions, each tile one riskier than
+        // the tile above or left of it, wrapping back to 1 after 9
+        let (rows, cols) = (self.0.rows(), self.0.cols());
+
+        self.0 = Grid::from_fn(rows * ratio, cols * ratio, |point| {
+            let original = Point::new(point.row % rows, point.col % cols);
+            let tile = point.row / rows + point.col / cols;
+            match (usize::from(self.0[original]) + tile) % 9 {
+                0 => 9,
+                risk => risk as u8,
+            }
+        });
+
+        assert!(self.0.iter().all(|risk| (1..=9).contains(risk)));
+    }
+}
+impl FromStr for Cavern {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self(s.parse()?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{first, second, Cavern};
+
+    fn load_test_data() -> Cavern {
+        include_str!("test.txt").parse().unwrap()
+    }
+
+    #[test]
+    fn day15_first() {
+        let input = load_test_data();
+        assert_eq!(first(input), 40);
+    }
+    #[test]
+    fn day15_second() {
+        let input = load_test_data();
+        assert_eq!(second(input), 315);
+    }
+}
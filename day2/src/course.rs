@@ -0,0 +1,213 @@
+use std::{collections::HashSet, str::FromStr};
+
+use common::{parse_token, ParseError};
+
+use crate::Action;
+
+const EXPECTED: &str = "`forward <n>`, `down <n>`, `up <n>`, `back <n>`, `set-aim <n>`, \
+`let <name> = <n>`, `repeat <n> {` or `}`";
+
+/// A course file: commands, `let` variables and `repeat` blocks, with `#` comments.
+///
+/// ```text
+/// let depth = 3
+/// repeat 2 {
+///     forward 5   # ahead
+///     down depth
+/// }
+/// back 2
+/// set-aim 0
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Course(Vec<Statement>);
+impl Course {
+    pub fn statements(&self) -> &[Statement] {
+        &self.0
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Statement {
+    /// a command such as `forward 5` or `down depth`, and its line in the course counting from 1
+    Move(Action, Value, usize),
+    /// `let <name> = <value>`
+    Let(String, Value),
+    /// `repeat <count> { ... }`
+    Repeat(Value, Vec<Statement>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Value {
+    Number(i32),
+    /// a variable defined by an earlier `let` of the same or an enclosing block
+    Variable(String),
+}
+
+/// The statements of a block and the variables visible in it, and for a `repeat` block its count and
+/// an error in case it is never closed.
+struct Block {
+    statements: Vec<Statement>,
+    repeat: Option<(Value, ParseError)>,
+    variables: HashSet<String>,
+}
+
+impl FromStr for Course {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut blocks = vec![Block {
+            statements: Vec::new(),
+            repeat: None,
+            variables: HashSet::new(),
+        }];
+
+        for (linenum, line) in s.lines().enumerate() {
+            let on_line = |err: ParseError| err.on_line(linenum + 1);
+            let code = line.split('#').next().unwrap_or("").trim_end();
+            let block = blocks
+                .last_mut()
+                .expect("the outermost block is never closed");
+            let variables = &mut block.variables;
+            let mut tokens = code.split_whitespace();
+            let keyword = match tokens.next() {
+                Some(keyword) => keyword,
+                None => continue,
+            };
+
+            let statement = match keyword {
+                "let" => {
+                    let name = tokens.next().ok_or_else(|| {
+                        on_line(ParseError::missing(code, "Missing variable", EXPECTED))
+                    })?;
+                    if !is_identifier(name) {
+                        return Err(on_line(ParseError::new(
+                            line,
+                            name,
+                            "Invalid variable",
+                            EXPECTED,
+                        )));
+                    }
+                    match tokens.next() {
+                        Some("=") => {}
+                        Some(token) => {
+                            return Err(on_line(ParseError::new(
+                                line,
+                                token,
+                                "Missing `=`",
+                                EXPECTED,
+                            )))
+                        }
+                        None => {
+                            return Err(on_line(ParseError::missing(code, "Missing `=`", EXPECTED)))
+                        }
+                    }
+                    let value = value(line, code, tokens.next(), variables).map_err(on_line)?;
+                    variables.insert(name.to_string());
+                    Statement::Let(name.to_string(), value)
+                }
+                "repeat" => {
+                    let count = value(line, code, tokens.next(), variables).map_err(on_line)?;
+                    match tokens.next() {
+                        Some("{") => {}
+                        Some(token) => {
+                            return Err(on_line(ParseError::new(
+                                line,
+                                token,
+                                "Missing `{`",
+                                EXPECTED,
+                            )))
+                        }
+                        None => {
+                            return Err(on_line(ParseError::missing(code, "Missing `{`", EXPECTED)))
+                        }
+                    }
+                    if let Some(extra) = tokens.next() {
+                        return Err(on_line(ParseError::new(
+                            line,
+                            extra,
+                            "Unexpected value",
+                            EXPECTED,
+                        )));
+                    }
+
+                    // variables defined in the block are only visible until its end
+                    let variables = variables.clone();
+                    let unclosed = ParseError::new(line, keyword, "Unclosed `repeat`", EXPECTED);
+                    blocks.push(Block {
+                        statements: Vec::new(),
+                        repeat: Some((count, on_line(unclosed))),
+                        variables,
+                    });
+                    continue;
+                }
+                "}" => {
+                    if block.repeat.is_none() {
+                        let err = ParseError::new(line, keyword, "Unexpected `}`", EXPECTED);
+                        return Err(on_line(err));
+                    }
+                    let block = blocks.pop().expect("a `repeat` block is open");
+                    let (count, _unclosed) = block.repeat.expect("a `repeat` block is open");
+                    Statement::Repeat(count, block.statements)
+                }
+                _ => {
+                    let action = Action::parse(line, keyword, EXPECTED).map_err(on_line)?;
+                    Statement::Move(
+                        action,
+                        value(line, code, tokens.next(), variables).map_err(on_line)?,
+                        linenum + 1,
+                    )
+                }
+            };
+
+            if let Some(extra) = tokens.next() {
+                return Err(on_line(ParseError::new(
+                    line,
+                    extra,
+                    "Unexpected value",
+                    EXPECTED,
+                )));
+            }
+            blocks
+                .last_mut()
+                .expect("the outermost block is never closed")
+                .statements
+                .push(statement);
+        }
+
+        let block = blocks.pop().expect("the outermost block is never closed");
+        match block.repeat {
+            Some((_count, unclosed)) => Err(unclosed),
+            None => Ok(Self(block.statements)),
+        }
+    }
+}
+
+/// Parses `token` of `line` as a number or a variable defined earlier.
+fn value(
+    line: &str,
+    code: &str,
+    token: Option<&str>,
+    variables: &HashSet<String>,
+) -> Result<Value, ParseError> {
+    let token = token.ok_or_else(|| ParseError::missing(code, "Missing value", EXPECTED))?;
+    if is_identifier(token) {
+        if !variables.contains(token) {
+            return Err(ParseError::new(line, token, "Undefined variable", EXPECTED));
+        }
+        return Ok(Value::Variable(token.to_string()));
+    }
+
+    Ok(Value::Number(parse_token(
+        line,
+        token,
+        "Invalid value",
+        EXPECTED,
+    )?))
+}
+
+fn is_identifier(token: &str) -> bool {
+    token.starts_with(|chr: char| chr.is_ascii_alphabetic() || chr == '_')
+        && token
+            .chars()
+            .all(|chr| chr.is_ascii_alphanumeric() || chr == '_')
+}
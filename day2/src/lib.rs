@@ -0,0 +1,589 @@
+use std::{collections::HashMap, error::Error, fmt::Display, str::FromStr};
+
+use common::{parse_token, ParseError, Solver};
+
+mod course;
+
+pub use course::{Course, Statement, Value};
+
+/// [Day 2: Dive!](https://adventofcode.com/2021/day/2)
+///
+/// ```
+/// let course: day2::Course = "forward 5\ndown 5\nforward 8\nup 3\ndown 8\nforward 2"
+///     .parse()
+///     .unwrap();
+/// assert_eq!(day2::first(&course), Ok(150));
+/// assert_eq!(day2::second(&course), Ok(900));
+///
+/// let mut sub = day2::Submarine::new(day2::Movement::Aim).with_history(true);
+/// sub.follow(&course).unwrap();
+/// assert_eq!(sub.history().len(), 7);
+/// assert_eq!(sub.position().aim, 10);
+/// ```
+pub struct Day2;
+impl Solver for Day2 {
+    const DAY: u8 = 2;
+    const INPUT: &'static str = include_str!("input.txt");
+
+    type Input<'a> = Course;
+    type First = i128;
+    type Second = i128;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        input.parse()
+    }
+    fn first(input: &Self::Input<'_>) -> Result<Self::First, Box<dyn Error>> {
+        Ok(first(input)?)
+    }
+    fn second(input: &Self::Input<'_>) -> Result<Self::Second, Box<dyn Error>> {
+        Ok(second(input)?)
+    }
+}
+
+pub fn first(input: &Course) -> Result<i128, CourseError> {
+    let mut sub = Submarine::new(Movement::Plain);
+    sub.follow(input)?;
+    Ok(sub.position().product())
+}
+
+pub fn second(input: &Course) -> Result<i128, CourseError> {
+    let mut sub = Submarine::new(Movement::Aim);
+    sub.follow(input)?;
+    Ok(sub.position().product())
+}
+
+/// How the submarine interprets `down` and `up`, while `back` always moves it horizontally and
+/// `set-aim` always sets its aim.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Movement {
+    /// `down` and `up` change the depth, as in part 1
+    Plain,
+    /// `down` and `up` change the aim, and `forward` also dives along the aim, as in part 2
+    Aim,
+}
+impl FromStr for Movement {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "plain" => Ok(Movement::Plain),
+            "aim" => Ok(Movement::Aim),
+            _ => Err(format!("Invalid movement: {}", s)),
+        }
+    }
+}
+
+/// What the submarine does when a command would take it above the surface.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Surface {
+    /// stop with a [`CourseError::Breach`]
+    Error,
+    /// stay at the surface
+    Clamp,
+    /// fly, as the puzzle does not care
+    #[default]
+    Allow,
+}
+impl FromStr for Surface {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "error" => Ok(Surface::Error),
+            "clamp" => Ok(Surface::Clamp),
+            "allow" => Ok(Surface::Allow),
+            _ => Err(format!("Invalid surface policy: {}", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Position {
+    pub horizontal: i64,
+    pub depth: i64, // bigger depth means the submarine is deeper
+    pub aim: i64,
+}
+impl Position {
+    pub fn product(&self) -> i128 {
+        i128::from(self.horizontal) * i128::from(self.depth)
+    }
+}
+
+/// A command the submarine could not execute, at `step` counting the commands executed from 1 as
+/// in [`Submarine::history`], and on `line` of the course it follows, if any.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CourseError {
+    /// a position or the aim would go beyond the range of `i64`
+    Overflow {
+        step: usize,
+        line: Option<usize>,
+        command: Command,
+    },
+    /// the submarine would rise to `depth` above the surface, with [`Surface::Error`]
+    Breach {
+        step: usize,
+        line: Option<usize>,
+        command: Command,
+        depth: i64,
+    },
+}
+impl CourseError {
+    pub fn on_line(mut self, linenum: usize) -> Self {
+        match &mut self {
+            CourseError::Overflow { line, .. } | CourseError::Breach { line, .. } => {
+                *line = Some(linenum)
+            }
+        }
+        self
+    }
+}
+impl Display for CourseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (CourseError::Overflow { line, .. } | CourseError::Breach { line, .. }) = self;
+        if let Some(line) = line {
+            write!(f, "line {}: ", line)?;
+        }
+        match self {
+            CourseError::Overflow { step, command, .. } => {
+                write!(f, "Command {} `{}` overflows", step, command)
+            }
+            CourseError::Breach {
+                step,
+                command,
+                depth,
+                ..
+            } => write!(
+                f,
+                "Command {} `{}` breaks the surface, to depth {}",
+                step, command, depth
+            ),
+        }
+    }
+}
+impl Error for CourseError {}
+
+/// A submarine following a course, remembering every position it went through if recording.
+#[derive(Debug, Clone)]
+pub struct Submarine {
+    movement: Movement,
+    surface: Surface,
+    recording: bool,
+    /// the number of commands executed
+    steps: usize,
+    history: Vec<Position>,
+}
+impl Submarine {
+    pub fn new(movement: Movement) -> Self {
+        Self {
+            movement,
+            surface: Surface::default(),
+            recording: false,
+            steps: 0,
+            history: vec![Position::default()],
+        }
+    }
+    pub fn with_surface(mut self, surface: Surface) -> Self {
+        self.surface = surface;
+        self
+    }
+    /// Whether to remember every position for [`Submarine::history`], rather than only the last.
+    pub fn with_history(mut self, recording: bool) -> Self {
+        self.recording = recording;
+        self
+    }
+    /// Moves the submarine, or leaves it where it is if the command fails.
+    pub fn execute(&mut self, command: Command) -> Result<(), CourseError> {
+        let step = self.steps + 1;
+        let overflow = || CourseError::Overflow {
+            step,
+            line: None,
+            command,
+        };
+        let add = |value: i64, n: i32| value.checked_add(n.into()).ok_or_else(overflow);
+        let sub = |value: i64, n: i32| value.checked_sub(n.into()).ok_or_else(overflow);
+
+        let mut position = self.position();
+        match (self.movement, command) {
+            (Movement::Plain, Command::Forward(n)) => {
+                position.horizontal = add(position.horizontal, n)?
+            }
+            (Movement::Plain, Command::Down(n)) => position.depth = add(position.depth, n)?,
+            (Movement::Plain, Command::Up(n)) => position.depth = sub(position.depth, n)?,
+            (Movement::Aim, Command::Forward(n)) => {
+                position.horizontal = add(position.horizontal, n)?;
+                position.depth = position
+                    .aim
+                    .checked_mul(n.into())
+                    .and_then(|dive| position.depth.checked_add(dive))
+                    .ok_or_else(overflow)?;
+            }
+            (Movement::Aim, Command::Down(n)) => position.aim = add(position.aim, n)?,
+            (Movement::Aim, Command::Up(n)) => position.aim = sub(position.aim, n)?,
+            (_, Command::Back(n)) => position.horizontal = sub(position.horizontal, n)?,
+            (_, Command::SetAim(n)) => position.aim = n.into(),
+        }
+        if position.depth < 0 {
+            match self.surface {
+                Surface::Error => {
+                    let depth = position.depth;
+                    return Err(CourseError::Breach {
+                        step,
+                        line: None,
+                        command,
+                        depth,
+                    });
+                }
+                Surface::Clamp => position.depth = 0,
+                Surface::Allow => {}
+            }
+        }
+
+        if self.recording {
+            self.history.push(position);
+        } else {
+            self.history[0] = position;
+        }
+        self.steps = step;
+        Ok(())
+    }
+    pub fn execute_all(&mut self, commands: &[Command]) -> Result<(), CourseError> {
+        for command in commands {
+            self.execute(*command)?;
+        }
+        Ok(())
+    }
+    /// Executes every command of `course`, repeating blocks as many times as their count, or not
+    /// at all for a count below 1.
+    pub fn follow(&mut self, course: &Course) -> Result<(), CourseError> {
+        self.follow_block(course.statements(), &mut HashMap::new())
+    }
+    fn follow_block<'a>(
+        &mut self,
+        statements: &'a [Statement],
+        variables: &mut HashMap<&'a str, i32>,
+    ) -> Result<(), CourseError> {
+        let resolve = |variables: &HashMap<&str, i32>, value: &Value| match value {
+            Value::Number(n) => *n,
+            Value::Variable(name) => variables[name.as_str()],
+        };
+        // the variables the block shadows, to restore at its end
+        let mut shadowed = Vec::new();
+        for statement in statements {
+            match statement {
+                Statement::Move(action, value, line) => self
+                    .execute(action.with(resolve(variables, value)))
+                    .map_err(|err| err.on_line(*line))?,
+                Statement::Let(name, value) => {
+                    let value = resolve(variables, value);
+                    shadowed.push((name.as_str(), variables.insert(name, value)));
+                }
+                Statement::Repeat(count, body) => {
+                    for _ in 0..resolve(variables, count) {
+                        self.follow_block(body, variables)?;
+                    }
+                }
+            }
+        }
+
+        for (name, value) in shadowed.into_iter().rev() {
+            match value {
+                Some(value) => variables.insert(name, value),
+                None => variables.remove(name),
+            };
+        }
+        Ok(())
+    }
+    pub fn position(&self) -> Position {
+        *self.history.last().expect("history starts at the surface")
+    }
+    /// The starting position followed by the position after each command, or only the current
+    /// position unless recording.
+    pub fn history(&self) -> &[Position] {
+        &self.history
+    }
+    /// The history as CSV, or `None` unless recording, as the steps before are forgotten.
+    pub fn to_csv(&self) -> Option<String> {
+        if !self.recording {
+            return None;
+        }
+        let mut csv = "step,horizontal,depth,aim\n".to_string();
+        for (step, position) in self.history.iter().enumerate() {
+            csv += &format!(
+                "{},{},{},{}\n",
+                step, position.horizontal, position.depth, position.aim
+            );
+        }
+        Some(csv)
+    }
+    /// Draws the trajectory seen from the side, with the surface at the top, or `None` unless
+    /// recording.
+    pub fn to_svg(&self) -> Option<String> {
+        if !self.recording {
+            return None;
+        }
+        // the history starts at the origin, so the bounds include it
+        let (mut left, mut right, mut top, mut bottom) = (0, 0, 0, 0);
+        for position in &self.history {
+            left = left.min(position.horizontal);
+            right = right.max(position.horizontal);
+            top = top.min(position.depth);
+            bottom = bottom.max(position.depth);
+        }
+
+        let points: Vec<String> = self
+            .history
+            .iter()
+            .map(|position| format!("{},{}", position.horizontal, position.depth))
+            .collect();
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"800\" height=\"400\" \
+viewBox=\"{} {} {} {}\" preserveAspectRatio=\"none\">\n",
+            left,
+            top,
+            (right - left).max(1),
+            (bottom - top).max(1),
+        );
+        svg += &format!(
+            "  <line x1=\"{}\" y1=\"0\" x2=\"{}\" y2=\"0\" stroke=\"steelblue\" \
+vector-effect=\"non-scaling-stroke\"/>\n",
+            left, right
+        );
+        svg += &format!(
+            "  <polyline points=\"{}\" fill=\"none\" stroke=\"black\" \
+vector-effect=\"non-scaling-stroke\"/>\n",
+            points.join(" ")
+        );
+        Some(svg + "</svg>\n")
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Command {
+    Forward(i32),
+    Down(i32),
+    Up(i32),
+    Back(i32),
+    SetAim(i32),
+}
+impl FromStr for Command {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        const EXPECTED: &str = "`forward <n>`, `down <n>`, `up <n>`, `back <n>` or `set-aim <n>`";
+
+        let mut tokens = s.split_whitespace();
+        let action = tokens
+            .next()
+            .ok_or_else(|| ParseError::missing(s, "Missing command", EXPECTED))?;
+        let action = Action::parse(s, action, EXPECTED)?;
+        let dist = tokens
+            .next()
+            .ok_or_else(|| ParseError::missing(s, "Missing value", EXPECTED))?;
+        let dist = parse_token(s, dist, "Invalid value", EXPECTED)?;
+
+        match tokens.next() {
+            Some(extra) => Err(ParseError::new(s, extra, "Unexpected value", EXPECTED)),
+            None => Ok(action.with(dist)),
+        }
+    }
+}
+
+impl Display for Command {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Command::Forward(n) => write!(f, "forward {}", n),
+            Command::Down(n) => write!(f, "down {}", n),
+            Command::Up(n) => write!(f, "up {}", n),
+            Command::Back(n) => write!(f, "back {}", n),
+            Command::SetAim(n) => write!(f, "set-aim {}", n),
+        }
+    }
+}
+
+/// A [`Command`] without its value, which a [`Course`] may give as a variable.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Forward,
+    Down,
+    Up,
+    Back,
+    SetAim,
+}
+impl Action {
+    pub fn with(self, n: i32) -> Command {
+        match self {
+            Action::Forward => Command::Forward(n),
+            Action::Down => Command::Down(n),
+            Action::Up => Command::Up(n),
+            Action::Back => Command::Back(n),
+            Action::SetAim => Command::SetAim(n),
+        }
+    }
+    fn parse(line: &str, token: &str, expected: &str) -> Result<Self, ParseError> {
+        match token {
+            "forward" => Ok(Action::Forward),
+            "down" => Ok(Action::Down),
+            "up" => Ok(Action::Up),
+            "back" => Ok(Action::Back),
+            "set-aim" => Ok(Action::SetAim),
+            _ => Err(ParseError::new(line, token, "Invalid command", expected)),
+        }
+    }
+}
+
+#[test]
+fn day2_first() {
+    let input = Day2::parse(include_str!("test.txt")).unwrap();
+    assert_eq!(first(&input), Ok(150));
+}
+
+#[test]
+fn day2_second() {
+    let input = Day2::parse(include_str!("test.txt")).unwrap();
+    assert_eq!(second(&input), Ok(900));
+}
+
+#[test]
+fn day2_parse_errors() {
+    let err = Day2::parse("forward 5\ndown 5\nfowrard 8").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "line 3, column 1: Invalid command `fowrard` (expected `forward <n>`, `down <n>`, `up <n>`, \
+`back <n>`, `set-aim <n>`, `let <name> = <n>`, `repeat <n> {` or `}`)"
+    );
+    let err = Day2::parse("forward 5\n\nup").unwrap_err();
+    assert_eq!(
+        (err.line, err.column, err.message.as_str()),
+        (3, 3, "Missing value")
+    );
+    let err = Day2::parse("down x").unwrap_err();
+    assert_eq!((err.column, err.text.as_str()), (6, "x"));
+}
+
+#[test]
+fn day2_trajectory() {
+    let course = Day2::parse("forward 5\ndown 5\nforward 8").unwrap();
+    let mut sub = Submarine::new(Movement::Plain).with_history(true);
+    sub.follow(&course).unwrap();
+    assert_eq!(
+        sub.history(),
+        [
+            Position::default(),
+            Position {
+                horizontal: 5,
+                depth: 0,
+                aim: 0
+            },
+            Position {
+                horizontal: 5,
+                depth: 5,
+                aim: 0
+            },
+            Position {
+                horizontal: 13,
+                depth: 5,
+                aim: 0
+            },
+        ]
+    );
+    assert_eq!(
+        sub.to_csv().as_deref(),
+        Some("step,horizontal,depth,aim\n0,0,0,0\n1,5,0,0\n2,5,5,0\n3,13,5,0\n")
+    );
+    let svg = sub.to_svg().unwrap();
+    assert!(svg.contains("viewBox=\"0 0 13 5\""));
+    assert!(svg.contains("points=\"0,0 5,0 5,5 13,5\""));
+
+    // without recording there is no trajectory to export
+    let mut sub = Submarine::new(Movement::Plain);
+    sub.follow(&course).unwrap();
+    assert_eq!(sub.to_csv(), None);
+    assert_eq!(sub.to_svg(), None);
+}
+
+#[test]
+fn day2_course() {
+    let course: Course = "let n = 2 # steps\nrepeat n {\n  forward 5\n  let n = 1\n  down n\n}\n\
+back 3\nrepeat 0 {\n}\nset-aim n"
+        .parse()
+        .unwrap();
+    assert_eq!(course.statements().len(), 5);
+    let mut sub = Submarine::new(Movement::Aim).with_history(true);
+    sub.follow(&course).unwrap();
+    assert_eq!(sub.history().len(), 7);
+    assert_eq!(
+        sub.position(),
+        Position {
+            horizontal: 7,
+            depth: 5,
+            aim: 2
+        }
+    );
+    assert_eq!("set-aim -4".parse(), Ok(Command::SetAim(-4)));
+
+    // without recording, only the current position is kept however long the course
+    let course: Course = "repeat 1000000 {\nforward 1\n}".parse().unwrap();
+    let mut sub = Submarine::new(Movement::Plain);
+    sub.follow(&course).unwrap();
+    assert_eq!(sub.history().len(), 1);
+    assert_eq!(sub.position().horizontal, 1000000);
+
+    let error = |course: &str| {
+        let err = course.parse::<Course>().unwrap_err();
+        (err.line, err.column, err.message)
+    };
+    assert_eq!(
+        error("repeat 2 {\n  up x\n}"),
+        (2, 6, "Undefined variable".into())
+    );
+    assert_eq!(
+        error("repeat 2 {\n  let x = 1\n}\nup x"),
+        (4, 4, "Undefined variable".into())
+    );
+    assert_eq!(
+        error("forward 1\n  repeat 2 {\nup 1"),
+        (2, 3, "Unclosed `repeat`".into())
+    );
+    assert_eq!(error("forward 1\n}"), (2, 1, "Unexpected `}`".into()));
+    assert_eq!(error("repeat 3\n}"), (1, 9, "Missing `{`".into()));
+    assert_eq!(error("back # 3"), (1, 5, "Missing value".into()));
+}
+
+#[test]
+fn day2_surface() {
+    let course: Course = "forward 2\ndown 1\nup 3\nforward 1".parse().unwrap();
+    let follow = |surface| {
+        let mut sub = Submarine::new(Movement::Plain).with_surface(surface);
+        sub.follow(&course).map(|()| sub.position())
+    };
+    assert_eq!(follow(Surface::Allow).unwrap().depth, -2);
+    assert_eq!(follow(Surface::Clamp).unwrap().depth, 0);
+    let err = follow(Surface::Error).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "line 3: Command 3 `up 3` breaks the surface, to depth -2"
+    );
+
+    let course: Course = "let n = 2147483647\nset-aim n\nrepeat n {\nforward n\n}"
+        .parse()
+        .unwrap();
+    let mut sub = Submarine::new(Movement::Aim).with_history(true);
+    assert_eq!(
+        sub.follow(&course),
+        Err(CourseError::Overflow {
+            step: 4,
+            line: Some(4),
+            command: Command::Forward(i32::MAX)
+        })
+    );
+    assert_eq!(sub.history().len(), 4);
+    assert_eq!(sub.position().depth, 2 * i64::from(i32::MAX).pow(2));
+
+    // commands executed on their own have no line
+    let mut sub = Submarine::new(Movement::Plain).with_surface(Surface::Error);
+    let err = sub.execute_all(&[Command::Up(1)]).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Command 1 `up 1` breaks the surface, to depth -1"
+    );
+}
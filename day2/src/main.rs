@@ -1,122 +1,75 @@
-use std::{num::ParseIntError, str::FromStr};
+use std::{env, process};
 
-fn main() {
-    let input = include_str!("input.txt");
-    let input = input
-        .lines()
-        .enumerate()
-        .filter(|(_linenum, line)| !line.is_empty())
-        .map(|(linenum, line)| match line.parse::<Command>() {
-            Ok(command) => command,
-            Err(err) => panic!("Got {:?} error at line {} of input.txt", err, linenum),
-        })
-        .collect::<Vec<Command>>();
+use common::Solver;
+use day2::{CourseError, Day2, Movement, Submarine, Surface};
 
-    println!("{}", first(&input));
-    println!("{}", second(&input));
-}
+const USAGE: &str =
+    "Usage: day2 [--trajectory <csv|svg>] [--movement <plain|aim>] [--surface <error|clamp|allow>] [input]
 
-fn first(input: &[Command]) -> i32 {
-    let mut sub = Submarine::new();
-    for command in input {
-        match command {
-            Command::Forward(n) => sub.horizontal_position += n,
-            Command::Down(n) => sub.depth += n,
-            Command::Up(n) => sub.depth -= n,
-        };
-    }
+--trajectory <format>  print the position after every command as CSV or SVG instead of the answers
+--movement <movement>  follow the course as in part 1 (plain) or part 2 (aim, the default)
+--surface <policy>     stop with an error, stay at the surface or fly (the default) when a command
+                       would take the submarine above the surface";
 
-    sub.horizontal_position * sub.depth
-}
+fn main() -> Result<(), common::Report> {
+    let mut trajectory = None;
+    let mut movement = Movement::Aim;
+    let mut surface = Surface::Allow;
+    let mut path = None;
 
-struct Submarine {
-    horizontal_position: i32,
-    depth: i32, // bigger depth means the submarine is deeper
-    aim: i32,
-}
-impl Submarine {
-    fn new() -> Self {
-        Self {
-            horizontal_position: 0,
-            depth: 0,
-            aim: 0,
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--trajectory" => match args.next().as_deref() {
+                Some(format @ ("csv" | "svg")) => trajectory = Some(format.to_string()),
+                _ => usage("Expected a trajectory format of `csv` or `svg`"),
+            },
+            "--movement" => match args.next().map(|movement| movement.parse()) {
+                Some(Ok(parsed)) => movement = parsed,
+                Some(Err(err)) => usage(&err),
+                None => usage("Missing movement"),
+            },
+            "--surface" => match args.next().map(|surface| surface.parse()) {
+                Some(Ok(parsed)) => surface = parsed,
+                Some(Err(err)) => usage(&err),
+                None => usage("Missing surface policy"),
+            },
+            _ if path.is_none() => path = Some(arg),
+            _ => usage(&format!("Unexpected argument: {}", arg)),
         }
     }
-}
-
-fn second(input: &[Command]) -> i32 {
-    let mut sub = Submarine::new();
-    for command in input {
-        match command {
-            Command::Forward(n) => {
-                sub.horizontal_position += n;
-                sub.depth += sub.aim * n;
-            }
-            Command::Down(n) => sub.aim += n,
-            Command::Up(n) => sub.aim -= n,
-        };
-    }
 
-    sub.horizontal_position * sub.depth
-}
-
-enum Command {
-    Forward(i32),
-    Down(i32),
-    Up(i32),
-}
-impl FromStr for Command {
-    type Err = String;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut s = s.split_whitespace();
-        match s.next() {
-            Some(command) => {
-                let dist: i32 = s
-                    .next()
-                    .ok_or_else(|| "Missing value".to_string())?
-                    .parse()
-                    .map_err(|err: ParseIntError| err.to_string())?;
-                match command {
-                    "forward" => Ok(Command::Forward(dist)),
-                    "down" => Ok(Command::Down(dist)),
-                    "up" => Ok(Command::Up(dist)),
-                    _ => Err(format!("Invalid command: {}", command)),
-                }
-            }
-            None => Err("Missing command".to_string()),
+    let input = common::read_input(path.as_deref(), Day2::INPUT)?;
+    let course = Day2::parse(&input)?;
+    let follow = |movement| -> Result<Submarine, CourseError> {
+        let mut sub = Submarine::new(movement)
+            .with_surface(surface)
+            .with_history(trajectory.is_some());
+        sub.follow(&course)?;
+        Ok(sub)
+    };
+    match trajectory.as_deref() {
+        Some(format) => {
+            let sub = follow(movement)?;
+            let trajectory = if format == "csv" {
+                sub.to_csv()
+            } else {
+                sub.to_svg()
+            };
+            print!(
+                "{}",
+                trajectory.expect("the submarine records its trajectory")
+            );
+        }
+        None => {
+            println!("{}", follow(Movement::Plain)?.position().product());
+            println!("{}", follow(Movement::Aim)?.position().product());
         }
     }
+    Ok(())
 }
 
-#[test]
-fn day2_first() {
-    let input: Vec<Command> = vec![
-        "forward 5",
-        "down 5",
-        "forward 8",
-        "up 3",
-        "down 8",
-        "forward 2",
-    ]
-    .iter()
-    .map(|s| s.parse().unwrap())
-    .collect();
-    assert_eq!(first(&input), 150);
-}
-
-#[test]
-fn day2_second() {
-    let input: Vec<Command> = vec![
-        "forward 5",
-        "down 5",
-        "forward 8",
-        "up 3",
-        "down 8",
-        "forward 2",
-    ]
-    .iter()
-    .map(|s| s.parse().unwrap())
-    .collect();
-    assert_eq!(second(&input), 900);
+fn usage(err: &str) -> ! {
+    eprintln!("{}\n{}", err, USAGE);
+    process::exit(2);
 }
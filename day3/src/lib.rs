@@ -0,0 +1,355 @@
+use std::{error::Error, fmt::Display, str::FromStr};
+
+use common::{parse_lines, ParseError, Solver};
+use num_bigint::BigUint;
+
+mod trie;
+
+use trie::Trie;
+
+/// [Day 3: Binary Diagnostic](https://adventofcode.com/2021/day/3)
+///
+/// ```
+/// let report: day3::Diagnostics = include_str!("test.txt").parse().unwrap();
+/// assert_eq!(day3::first(&report), 198u32.into());
+/// assert_eq!(day3::second(&report), Ok(230u32.into()));
+///
+/// let oxygen = report.rating(&day3::Criteria::oxygen()).unwrap();
+/// assert_eq!(oxygen.trace, [7, 4, 3, 2, 1]);
+/// ```
+pub struct Day3;
+impl Solver for Day3 {
+    const DAY: u8 = 3;
+    const INPUT: &'static str = include_str!("input.txt");
+
+    type Input<'a> = Diagnostics;
+    type First = BigUint;
+    type Second = BigUint;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        input.parse()
+    }
+    fn first(input: &Self::Input<'_>) -> Result<Self::First, Box<dyn Error>> {
+        Ok(first(input))
+    }
+    fn second(input: &Self::Input<'_>) -> Result<Self::Second, Box<dyn Error>> {
+        Ok(second(input)?)
+    }
+}
+
+pub fn first(report: &Diagnostics) -> BigUint {
+    BigUint::from(report.gamma()) * report.epsilon()
+}
+
+pub fn second(report: &Diagnostics) -> Result<BigUint, RatingError> {
+    let oxygen_generator = report.rating(&Criteria::oxygen())?.row;
+    let co2_scrubber = report.rating(&Criteria::co2())?.row;
+
+    Ok(BigUint::from(oxygen_generator) * co2_scrubber)
+}
+
+/// The rows of a diagnostic report, up to [`Diagnostics::MAX_WIDTH`] bits wide.
+#[derive(Debug, Clone)]
+pub struct Diagnostics {
+    width: usize,
+    /// each row with its first column as the most significant bit
+    rows: Vec<u128>,
+    /// each column as a bitset of the rows with a `1` in it, 64 rows per word
+    columns: Vec<Vec<u64>>,
+    trie: Trie,
+}
+impl Diagnostics {
+    pub const MAX_WIDTH: usize = u128::BITS as usize;
+
+    /// Creates a report from rows of `width` bits, ignoring any higher bits.
+    pub fn new(width: usize, rows: Vec<u128>) -> Self {
+        assert!(width <= Self::MAX_WIDTH, "width {} over 128 bits", width);
+        let rows: Vec<u128> = rows.into_iter().map(|row| row & mask(width)).collect();
+
+        let mut columns = vec![vec![0; rows.len().div_ceil(64)]; width];
+        for (i, row) in rows.iter().enumerate() {
+            for (column, bits) in columns.iter_mut().enumerate() {
+                bits[i / 64] |= ((row >> (width - 1 - column)) as u64 & 1) << (i % 64);
+            }
+        }
+        let trie = Trie::new(width, &rows, (0..width).collect());
+
+        Self {
+            width,
+            rows,
+            columns,
+            trie,
+        }
+    }
+    pub fn width(&self) -> usize {
+        self.width
+    }
+    pub fn rows(&self) -> &[u128] {
+        &self.rows
+    }
+    /// The number of rows with a `1` in `column`, counting from 0 on the left.
+    pub fn ones(&self, column: usize) -> usize {
+        self.columns[column]
+            .iter()
+            .map(|bits| bits.count_ones() as usize)
+            .sum()
+    }
+    /// The most common bit of each column, with ties going to `0`.
+    pub fn gamma(&self) -> u128 {
+        (0..self.width).fold(0, |gamma, column| {
+            gamma << 1 | u128::from(self.ones(column) * 2 > self.rows.len())
+        })
+    }
+    /// The least common bit of each column, with ties going to `1`.
+    pub fn epsilon(&self) -> u128 {
+        !self.gamma() & mask(self.width)
+    }
+    /// The row left after keeping the rows meeting `criteria` in each column in turn, stopping
+    /// once a single row is left.
+    pub fn rating(&self, criteria: &Criteria) -> Result<Rating, RatingError> {
+        let columns = match &criteria.order {
+            Order::LeftToRight => (0..self.width).collect(),
+            Order::RightToLeft => (0..self.width).rev().collect(),
+            Order::Columns(columns) => columns.clone(),
+        };
+        if let Some(&column) = columns.iter().find(|column| **column >= self.width) {
+            let width = self.width;
+            return Err(RatingError::ColumnOutOfRange { column, width });
+        }
+
+        let keep = |zeros: usize, ones: usize| {
+            let bit = match criteria.keep {
+                _ if zeros == ones => criteria.tie,
+                Keep::MostCommon if ones > zeros => Bit::One,
+                Keep::LeastCommon if ones < zeros => Bit::One,
+                _ => Bit::Zero,
+            };
+            bit as usize
+        };
+        let (row, trace) = match criteria.order {
+            Order::LeftToRight => self.trie.search(keep)?,
+            _ => Trie::new(self.width, &self.rows, columns).search(keep)?,
+        };
+        Ok(Rating {
+            row: self.rows[row],
+            trace,
+        })
+    }
+    pub fn oxygen_rating(&self) -> Result<u128, RatingError> {
+        self.rating(&Criteria::oxygen()).map(|rating| rating.row)
+    }
+    pub fn co2_rating(&self) -> Result<u128, RatingError> {
+        self.rating(&Criteria::co2()).map(|rating| rating.row)
+    }
+}
+
+/// Which rows a [`Diagnostics::rating`] keeps in each column.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Criteria {
+    pub keep: Keep,
+    /// the bit to keep when there are as many of each
+    pub tie: Bit,
+    pub order: Order,
+}
+impl Criteria {
+    /// The most common bits, with ties going to `1`, as for the oxygen generator rating.
+    pub fn oxygen() -> Self {
+        Self {
+            keep: Keep::MostCommon,
+            tie: Bit::One,
+            order: Order::LeftToRight,
+        }
+    }
+    /// The least common bits, with ties going to `0`, as for the CO2 scrubber rating.
+    pub fn co2() -> Self {
+        Self {
+            keep: Keep::LeastCommon,
+            tie: Bit::Zero,
+            order: Order::LeftToRight,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Keep {
+    MostCommon,
+    LeastCommon,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Bit {
+    Zero = 0,
+    One = 1,
+}
+
+/// The columns to scan, counting from 0 on the left.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Order {
+    LeftToRight,
+    RightToLeft,
+    Columns(Vec<usize>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rating {
+    pub row: u128,
+    /// the number of rows left after each column scanned, the last being 1 unless the columns ran
+    /// out first
+    pub trace: Vec<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RatingError {
+    Empty,
+    /// no row has the bit the criteria keep in `column`, after `trace` as in [`Rating::trace`]
+    Eliminated {
+        column: usize,
+        trace: Vec<usize>,
+    },
+    ColumnOutOfRange {
+        column: usize,
+        width: usize,
+    },
+}
+impl Display for RatingError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RatingError::Empty => write!(f, "No rows to rate"),
+            RatingError::Eliminated { column, trace } => write!(
+                f,
+                "No row left after column {} (rows left before: {:?})",
+                column, trace
+            ),
+            RatingError::ColumnOutOfRange { column, width } => {
+                write!(f, "Column {} out of range for width {}", column, width)
+            }
+        }
+    }
+}
+impl Error for RatingError {}
+impl FromStr for Diagnostics {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        const EXPECTED: &str = "binary numbers of the same width up to 128 bits such as `00100`";
+
+        let mut width = None;
+        let rows = parse_lines(s, |line| {
+            let num = line.trim();
+            if let Some((i, bit)) = num.char_indices().find(|(_i, bit)| !"01".contains(*bit)) {
+                let bit = &num[i..i + bit.len_utf8()];
+                return Err(ParseError::new(line, bit, "Invalid bit", EXPECTED));
+            }
+            if num.len() > Diagnostics::MAX_WIDTH {
+                return Err(ParseError::new(line, num, "Too wide", EXPECTED));
+            }
+            if *width.get_or_insert(num.len()) != num.len() {
+                return Err(ParseError::new(line, num, "Inconsistent width", EXPECTED));
+            }
+
+            Ok(u128::from_str_radix(num, 2).expect("valid bits"))
+        })?;
+
+        Ok(Self::new(width.unwrap_or(0), rows))
+    }
+}
+
+/// The lowest `width` bits.
+fn mask(width: usize) -> u128 {
+    u128::MAX
+        .checked_shr((Diagnostics::MAX_WIDTH - width) as u32)
+        .unwrap_or(0)
+}
+
+#[test]
+fn day3_first() {
+    let input = Day3::parse(include_str!("test.txt")).unwrap();
+    assert_eq!(first(&input), 198u32.into());
+}
+
+#[test]
+fn day3_second() {
+    let input = Day3::parse(include_str!("test.txt")).unwrap();
+    assert_eq!(second(&input), Ok(230u32.into()));
+}
+
+#[test]
+fn day3_criteria() {
+    let report = Day3::parse(include_str!("test.txt")).unwrap();
+    let oxygen = report.rating(&Criteria::oxygen()).unwrap();
+    assert_eq!((oxygen.row, oxygen.trace), (0b10111, vec![7, 4, 3, 2, 1]));
+    let co2 = report.rating(&Criteria::co2()).unwrap();
+    assert_eq!((co2.row, co2.trace), (0b01010, vec![5, 2, 1]));
+
+    let criteria = Criteria {
+        order: Order::RightToLeft,
+        ..Criteria::oxygen()
+    };
+    let rating = report.rating(&criteria).unwrap();
+    assert_eq!(rating.row & 1, 0);
+    assert_eq!(rating.trace.last(), Some(&1));
+    // the columns run out with 7 rows left, and the first of them is the rating
+    let criteria = Criteria {
+        order: Order::Columns(vec![4]),
+        ..Criteria::oxygen()
+    };
+    let rating = report.rating(&criteria).unwrap();
+    assert_eq!((rating.row, rating.trace), (0b00100, vec![7]));
+    let criteria = Criteria {
+        order: Order::Columns(vec![4, 0, 1]),
+        ..Criteria::co2()
+    };
+    let rating = report.rating(&criteria).unwrap();
+    assert_eq!((rating.row, rating.trace), (0b00111, vec![5, 2, 1]));
+    assert!(report.rows().contains(&rating.row));
+    let criteria = Criteria {
+        order: Order::Columns(vec![0, 5]),
+        ..Criteria::oxygen()
+    };
+    assert_eq!(
+        report.rating(&criteria),
+        Err(RatingError::ColumnOutOfRange {
+            column: 5,
+            width: 5
+        })
+    );
+
+    // the least common bit of the first column has no rows
+    let report = Day3::parse("10\n11\n").unwrap();
+    assert_eq!(
+        report.co2_rating(),
+        Err(RatingError::Eliminated {
+            column: 0,
+            trace: vec![]
+        })
+    );
+    assert_eq!(
+        Day3::parse("").unwrap().oxygen_rating(),
+        Err(RatingError::Empty)
+    );
+}
+
+#[test]
+fn day3_wide_rows() {
+    let ones = "1".repeat(128);
+    let wide = format!("{}\n0{}\n{}0\n", ones, &ones[1..], &ones[1..]);
+    let report = Day3::parse(&wide).unwrap();
+    assert_eq!(report.width(), 128);
+    assert_eq!(
+        (report.ones(0), report.ones(64), report.ones(127)),
+        (2, 3, 2)
+    );
+    assert_eq!(report.gamma(), u128::MAX);
+    assert_eq!(report.epsilon(), 0);
+    assert_eq!(report.oxygen_rating(), Ok(u128::MAX));
+    assert_eq!(report.co2_rating(), Ok(u128::MAX >> 1));
+    assert_eq!(
+        second(&report),
+        Ok(BigUint::from(u128::MAX) * BigUint::from(u128::MAX >> 1))
+    );
+
+    let err = Day3::parse(&format!("{}1", ones)).unwrap_err();
+    assert_eq!(err.message, "Too wide");
+    let err = Day3::parse("0101\n011\n").unwrap_err();
+    assert_eq!((err.line, err.message.as_str()), (2, "Inconsistent width"));
+}
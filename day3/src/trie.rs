@@ -0,0 +1,77 @@
+use crate::RatingError;
+
+/// A binary trie of the rows of a report, branching on their bits in the order of `columns` and
+/// counting the rows below each node.
+#[derive(Debug, Clone)]
+pub(crate) struct Trie {
+    columns: Vec<usize>,
+    nodes: Vec<Node>,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct Node {
+    /// the nodes for a `0` and a `1` in the next column, where 0 (the root) means none
+    children: [usize; 2],
+    count: usize,
+    /// the index of the first row below
+    row: usize,
+}
+
+impl Trie {
+    /// `columns` count from 0 on the left, and are all less than `width`.
+    pub(crate) fn new(width: usize, rows: &[u128], columns: Vec<usize>) -> Self {
+        let mut nodes = vec![Node::default()];
+        for (i, row) in rows.iter().enumerate() {
+            let mut node = 0;
+            nodes[node].count += 1;
+            for column in &columns {
+                let bit = (row >> (width - 1 - column)) as usize & 1;
+                if nodes[node].children[bit] == 0 {
+                    nodes.push(Node {
+                        row: i,
+                        ..Node::default()
+                    });
+                    nodes[node].children[bit] = nodes.len() - 1;
+                }
+                node = nodes[node].children[bit];
+                nodes[node].count += 1;
+            }
+        }
+
+        Self { columns, nodes }
+    }
+    /// Keeps the rows with the bit `keep(zeros, ones)` picks in each column until one row is left,
+    /// returning the index of that row, or of the first row left if the columns run out first,
+    /// with the number of rows left after each of those columns.
+    pub(crate) fn search(
+        &self,
+        keep: impl Fn(usize, usize) -> usize,
+    ) -> Result<(usize, Vec<usize>), RatingError> {
+        if self.nodes[0].count == 0 {
+            return Err(RatingError::Empty);
+        }
+
+        let mut node = 0;
+        let mut trace = Vec::new();
+        for &column in &self.columns {
+            if self.nodes[node].count == 1 {
+                break;
+            }
+            let [zeros, ones] = self.nodes[node].children.map(|child| {
+                if child == 0 {
+                    0
+                } else {
+                    self.nodes[child].count
+                }
+            });
+            let child = self.nodes[node].children[keep(zeros, ones)];
+            if child == 0 {
+                return Err(RatingError::Eliminated { column, trace });
+            }
+            trace.push(self.nodes[child].count);
+            node = child;
+        }
+
+        Ok((self.nodes[node].row, trace))
+    }
+}
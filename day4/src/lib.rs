@@ -0,0 +1,744 @@
+use std::{collections::HashMap, error::Error, fmt::Display, str::FromStr};
+
+use common::{parse_token, ParseError, Solver};
+use grid::{Grid, Point};
+use serde_json::json;
+
+mod replay;
+
+pub use replay::Replay;
+
+/// [Day 4: Giant Squid](https://adventofcode.com/2021/day/4)
+///
+/// ```
+/// let bingo: day4::Bingo = include_str!("test.txt").parse().unwrap();
+/// assert_eq!(day4::first(&bingo), Ok(4512));
+/// assert_eq!(day4::second(&bingo), Ok(1924));
+///
+/// let wins = bingo.play(&[day4::Pattern::Diagonals]);
+/// assert_eq!(wins.len(), 3);
+/// ```
+pub struct Day4;
+impl Solver for Day4 {
+    const DAY: u8 = 4;
+    const INPUT: &'static str = include_str!("input.txt");
+
+    type Input<'a> = Bingo;
+    type First = i32;
+    type Second = i32;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        input.parse()
+    }
+    fn first(input: &Self::Input<'_>) -> Result<Self::First, Box<dyn Error>> {
+        Ok(first(input)?)
+    }
+    fn second(input: &Self::Input<'_>) -> Result<Self::Second, Box<dyn Error>> {
+        Ok(second(input)?)
+    }
+}
+
+pub fn first(bingo: &Bingo) -> Result<i32, NoWinner> {
+    let wins = bingo.play(&[Pattern::Rows, Pattern::Columns]);
+    let first = wins.first().ok_or(NoWinner)?;
+    Ok(first.score)
+}
+
+pub fn second(bingo: &Bingo) -> Result<i32, NoWinner> {
+    let wins = bingo.play(&[Pattern::Rows, Pattern::Columns]);
+    let last = wins.last().ok_or(NoWinner)?;
+    Ok(last.score)
+}
+
+/// No board won once every number was called.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NoWinner;
+impl Display for NoWinner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "No board wins with the numbers called")
+    }
+}
+impl Error for NoWinner {}
+
+#[derive(Debug, Clone)]
+pub struct Bingo {
+    pub rolls: Vec<i32>,
+    pub boards: Vec<Board>,
+}
+impl Bingo {
+    /// The win of every board that won, in the order they won, as in [`Bingo::replay`].
+    pub fn play(&self, patterns: &[Pattern]) -> Vec<Win> {
+        let mut wins = Vec::new();
+        self.replay(patterns, |event| {
+            if let Event::Won(win) = event {
+                wins.push(win);
+            }
+        });
+        wins
+    }
+    /// Calls every number until every board has won, with a board winning once all the cells of
+    /// a line of any of `patterns` are marked, passing what happens to `emit` as it happens.
+    pub fn replay(&self, patterns: &[Pattern], mut emit: impl FnMut(Event)) {
+        let mut layouts = HashMap::new();
+        for board in &self.boards {
+            let (rows, cols) = (board.rows(), board.cols());
+            layouts
+                .entry((rows, cols))
+                .or_insert_with(|| Layout::new(patterns, rows, cols));
+        }
+        let layout = |board: &Board| &layouts[&(board.rows(), board.cols())];
+
+        let mut boards = self.boards.clone();
+        // the number of marked cells in each line of each board
+        let mut hits: Vec<Vec<usize>> = boards
+            .iter()
+            .map(|board| vec![0; layout(board).lines.len()])
+            .collect();
+        // the boards with each number, so that a number only visits the boards it marks
+        let mut with_number: HashMap<i32, Vec<usize>> = HashMap::new();
+        for (i, board) in boards.iter().enumerate() {
+            for number in board.index.keys() {
+                with_number.entry(*number).or_default().push(i);
+            }
+        }
+        let mut won = vec![false; boards.len()];
+        let mut wins = 0;
+
+        for (turn, &number) in self.rolls.iter().enumerate() {
+            if wins == boards.len() {
+                break;
+            }
+            let turn = turn + 1;
+            emit(Event::Called { turn, number });
+
+            for &board in with_number.get(&number).into_iter().flatten() {
+                let card = &mut boards[board];
+                if won[board] {
+                    continue;
+                }
+                let Some(cell) = card.mark(number) else {
+                    continue;
+                };
+                emit(Event::Marked {
+                    turn,
+                    board,
+                    number,
+                    cell,
+                });
+
+                let layout = layout(card);
+                for &line in &layout.through[cell] {
+                    hits[board][line] += 1;
+                    if hits[board][line] == layout.lengths[line] && !won[board] {
+                        won[board] = true;
+                        wins += 1;
+                        emit(Event::Won(Win {
+                            board,
+                            turn,
+                            number,
+                            line: layout.lines[line].clone(),
+                            score: number * card.sum_unmarked(),
+                        }));
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// The lines of some patterns on boards of one size.
+struct Layout {
+    lines: Vec<Line>,
+    /// the number of distinct cells of each line, including any outside the board
+    lengths: Vec<usize>,
+    /// the indices of the lines through each cell
+    through: Grid<Vec<usize>>,
+}
+impl Layout {
+    fn new(patterns: &[Pattern], rows: usize, cols: usize) -> Self {
+        let lines: Vec<Line> = patterns
+            .iter()
+            .flat_map(|pattern| pattern.lines(rows, cols))
+            .collect();
+        let mut lengths = Vec::with_capacity(lines.len());
+        let mut through = Grid::new(rows, cols, Vec::new());
+        for (i, line) in lines.iter().enumerate() {
+            let mut cells = line.cells(rows, cols);
+            cells.sort();
+            cells.dedup();
+            lengths.push(cells.len());
+            for cell in cells {
+                if let Some(through) = through.get_mut(cell) {
+                    through.push(i);
+                }
+            }
+        }
+
+        Self {
+            lines,
+            lengths,
+            through,
+        }
+    }
+}
+impl FromStr for Bingo {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        const ROLLS: &str = "comma separated numbers such as `7,4,9,5,11`";
+        const BOARD: &str = "boards of rows of numbers such as `22 13 17 11  0`, all the size of \
+the first board, which ends at a blank line";
+
+        let mut s = s.lines().enumerate();
+
+        let (_, first_line) = s
+            .next()
+            .ok_or_else(|| ParseError::missing("", "Missing numbers", ROLLS))?;
+        let rolls = first_line
+            .split(',')
+            .map(|n| parse_token(first_line, n.trim(), "Invalid number", ROLLS))
+            .collect::<Result<_, _>>()?;
+
+        fn parse_board_row(s: &str, cols: Option<usize>) -> Result<Vec<i32>, ParseError> {
+            let row = s
+                .split_whitespace()
+                .map(|i| parse_token(s, i, "Invalid number", BOARD))
+                .collect::<Result<Vec<_>, ParseError>>()?;
+            if cols.is_some_and(|cols| row.len() != cols) {
+                return Err(ParseError::new(s, s.trim(), "Invalid board row", BOARD));
+            }
+            Ok(row)
+        }
+
+        let mut boards = Vec::new();
+        // the rows and columns of every board, from the first one
+        let mut size: Option<(usize, usize)> = None;
+        // (line number, line) of the last board row, to report boards cut short
+        let mut last_row = (1, first_line);
+        while let Some((linenum, line)) = s.next() {
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let mut board = Vec::new();
+            let mut row = Some((linenum, line));
+            while let Some((linenum, line)) = row.filter(|(_linenum, line)| !line.trim().is_empty())
+            {
+                last_row = (linenum + 1, line);
+                let cols = size
+                    .map(|(_rows, cols)| cols)
+                    .or(board.first().map(Vec::len));
+                board.push(parse_board_row(line, cols).map_err(|err| err.on_line(linenum + 1))?);
+                if size.is_some_and(|(rows, _cols)| board.len() == rows) {
+                    break;
+                }
+                row = s.next();
+            }
+
+            let (rows, _cols) = *size.get_or_insert((board.len(), board[0].len()));
+            if board.len() < rows {
+                let (linenum, line) = last_row;
+                return Err(ParseError::missing(line, "Incomplete board", BOARD).on_line(linenum));
+            }
+            boards.push(Board::from_rows(board).expect("rows have the same length"))
+        }
+
+        if boards.is_empty() {
+            let (linenum, line) = last_row;
+            return Err(ParseError::missing(line, "Missing boards", BOARD).on_line(linenum));
+        }
+
+        Ok(Self { rolls, boards })
+    }
+}
+
+/// A board that won, on the `turn`th number called.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Win {
+    /// the index of the board in [`Bingo::boards`]
+    pub board: usize,
+    pub turn: usize,
+    pub number: i32,
+    pub line: Line,
+    /// the sum of the unmarked numbers of the board times the number called
+    pub score: i32,
+}
+
+/// What happens in a game of bingo, in order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Event {
+    /// the `turn`th number is called
+    Called {
+        turn: usize,
+        number: i32,
+    },
+    /// a board that has not won yet has the number called at `cell`
+    Marked {
+        turn: usize,
+        board: usize,
+        number: i32,
+        cell: Point,
+    },
+    Won(Win),
+}
+impl Event {
+    pub fn turn(&self) -> usize {
+        match self {
+            Event::Called { turn, .. } | Event::Marked { turn, .. } => *turn,
+            Event::Won(win) => win.turn,
+        }
+    }
+    /// The event as a JSON object, to write a line of [JSON Lines](https://jsonlines.org) each.
+    pub fn to_json(&self) -> serde_json::Value {
+        match self {
+            Event::Called { turn, number } => {
+                json!({ "event": "called", "turn": turn, "number": number })
+            }
+            Event::Marked {
+                turn,
+                board,
+                number,
+                cell,
+            } => json!({
+                "event": "marked",
+                "turn": turn,
+                "board": board,
+                "number": number,
+                "row": cell.row,
+                "col": cell.col,
+            }),
+            Event::Won(win) => json!({
+                "event": "won",
+                "turn": win.turn,
+                "board": win.board,
+                "number": win.number,
+                "line": win.line.to_string(),
+                "score": win.score,
+            }),
+        }
+    }
+}
+
+/// The lines a board wins with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Pattern {
+    Rows,
+    Columns,
+    /// the diagonals from the top corners, as long as the shorter side of the board
+    Diagonals,
+    Corners,
+    Full,
+    /// the given cells, which never win if any lies outside the board
+    Mask(Vec<Point>),
+}
+impl Pattern {
+    pub fn lines(&self, rows: usize, cols: usize) -> Vec<Line> {
+        match self {
+            Pattern::Rows => (0..rows).map(Line::Row).collect(),
+            Pattern::Columns => (0..cols).map(Line::Column).collect(),
+            Pattern::Diagonals => vec![Line::Diagonal, Line::AntiDiagonal],
+            Pattern::Corners => vec![Line::Corners],
+            Pattern::Full => vec![Line::Full],
+            Pattern::Mask(cells) => vec![Line::Mask(cells.clone())],
+        }
+    }
+}
+
+/// A set of cells that wins once all marked.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Line {
+    Row(usize),
+    Column(usize),
+    /// from the top left corner
+    Diagonal,
+    /// from the top right corner
+    AntiDiagonal,
+    Corners,
+    Full,
+    Mask(Vec<Point>),
+}
+impl Display for Line {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Line::Row(row) => write!(f, "row {}", row),
+            Line::Column(col) => write!(f, "column {}", col),
+            Line::Diagonal => write!(f, "diagonal"),
+            Line::AntiDiagonal => write!(f, "anti-diagonal"),
+            Line::Corners => write!(f, "corners"),
+            Line::Full => write!(f, "full card"),
+            Line::Mask(cells) => {
+                write!(f, "mask")?;
+                for cell in cells {
+                    write!(f, " ({}, {})", cell.row, cell.col)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+impl Line {
+    pub fn cells(&self, rows: usize, cols: usize) -> Vec<Point> {
+        let side = rows.min(cols);
+        match self {
+            Line::Row(row) => (0..cols).map(|col| Point::new(*row, col)).collect(),
+            Line::Column(col) => (0..rows).map(|row| Point::new(row, *col)).collect(),
+            Line::Diagonal => (0..side).map(|i| Point::new(i, i)).collect(),
+            Line::AntiDiagonal => (0..side).map(|i| Point::new(i, cols - 1 - i)).collect(),
+            Line::Corners => {
+                let mut corners = vec![
+                    Point::new(0, 0),
+                    Point::new(0, cols - 1),
+                    Point::new(rows - 1, 0),
+                    Point::new(rows - 1, cols - 1),
+                ];
+                corners.sort();
+                corners.dedup();
+                corners
+            }
+            Line::Full => Grid::new(rows, cols, ()).points().collect(),
+            Line::Mask(cells) => cells.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Board {
+    cells: Grid<(bool, i32)>,
+    /// the cell of each number, the first one for numbers on the board more than once
+    index: HashMap<i32, Point>,
+}
+impl Board {
+    /// Creates an unmarked board, or `None` if the rows are empty or not all the same length.
+    pub fn from_rows(rows: Vec<Vec<i32>>) -> Option<Self> {
+        let rows = rows
+            .into_iter()
+            .map(|row| row.into_iter().map(|number| (false, number)).collect())
+            .collect();
+        let cells = Grid::from_rows(rows).filter(|grid| grid.rows() > 0 && grid.cols() > 0)?;
+
+        let mut index = HashMap::with_capacity(cells.rows() * cells.cols());
+        for point in cells.points() {
+            index.entry(cells[point].1).or_insert(point);
+        }
+        Some(Self { cells, index })
+    }
+    pub fn rows(&self) -> usize {
+        self.cells.rows()
+    }
+    pub fn cols(&self) -> usize {
+        self.cells.cols()
+    }
+    /// Marks the cell of `number`, returning it unless the board has no such number or it is
+    /// already marked.
+    pub fn mark(&mut self, number: i32) -> Option<Point> {
+        let point = *self.index.get(&number)?;
+        let (marked, _) = &mut self.cells[point];
+        if *marked {
+            return None;
+        }
+
+        *marked = true;
+        Some(point)
+    }
+    pub fn is_marked(&self, point: Point) -> bool {
+        self.cells.get(point).is_some_and(|(marked, _)| *marked)
+    }
+    /// Whether a row or a column is marked, as in the puzzle.
+    pub fn is_winner(&self) -> bool {
+        self.winning_line(&[Pattern::Rows, Pattern::Columns])
+            .is_some()
+    }
+    /// The first line of `patterns` with every cell marked.
+    pub fn winning_line(&self, patterns: &[Pattern]) -> Option<Line> {
+        let (rows, cols) = (self.rows(), self.cols());
+        patterns
+            .iter()
+            .flat_map(|pattern| pattern.lines(rows, cols))
+            .find(|line| {
+                let cells = line.cells(rows, cols);
+                !cells.is_empty() && cells.into_iter().all(|cell| self.is_marked(cell))
+            })
+    }
+    pub fn sum_unmarked(&self) -> i32 {
+        self.cells
+            .iter()
+            .map(|(is_marked, num)| if !*is_marked { *num } else { 0 })
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use grid::Point;
+    use proptest::prelude::*;
+
+    use crate::{first, second, Bingo, Board, Event, Line, NoWinner, Pattern, Replay, Win};
+
+    fn load_test_data() -> Bingo {
+        include_str!("test.txt").parse::<Bingo>().unwrap()
+    }
+
+    #[test]
+    fn day4_first() {
+        let input = load_test_data();
+        assert_eq!(first(&input), Ok(4512));
+    }
+
+    #[test]
+    fn day4_second() {
+        let input = load_test_data();
+        assert_eq!(second(&input), Ok(1924));
+    }
+
+    #[test]
+    fn day4_no_winner() {
+        let bingo: Bingo = "9\n\n1 2\n3 4\n".parse().unwrap();
+        assert_eq!(first(&bingo), Err(NoWinner));
+        assert_eq!(
+            second(&bingo).unwrap_err().to_string(),
+            "No board wins with the numbers called"
+        );
+    }
+
+    #[test]
+    fn day4_parse_errors() {
+        let err = "7,4,x\n\n1 2 3 4 5".parse::<Bingo>().unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (1, 5, "x"));
+
+        let input = include_str!("test.txt").replacen("14 21 17 24  4", "14 21 17 24", 1);
+        let err = input.parse::<Bingo>().unwrap_err();
+        assert_eq!((err.line, err.message.as_str()), (15, "Invalid board row"));
+
+        let input = include_str!("test.txt")
+            .trim_end()
+            .rsplit_once('\n')
+            .unwrap()
+            .0;
+        let err = input.parse::<Bingo>().unwrap_err();
+        assert_eq!((err.line, err.message.as_str()), (18, "Incomplete board"));
+    }
+
+    #[test]
+    fn day4_patterns() {
+        let bingo = load_test_data();
+        let wins = bingo.play(&[Pattern::Rows, Pattern::Columns]);
+        let turns: Vec<(usize, usize, i32)> = wins
+            .iter()
+            .map(|win| (win.board, win.turn, win.score))
+            .collect();
+        assert_eq!(turns, vec![(2, 12, 4512), (0, 14, 2192), (1, 15, 1924)]);
+        assert_eq!(wins[0].line, Line::Row(0));
+
+        let corners = Board::from_rows(vec![vec![1, 2, 3], vec![4, 5, 6]]).unwrap();
+        let bingo = Bingo {
+            rolls: vec![1, 3, 5, 4, 6, 2],
+            boards: vec![corners],
+        };
+        let win = |pattern| bingo.play(&[pattern])[0].clone();
+        assert_eq!(win(Pattern::Corners).turn, 5);
+        assert_eq!(
+            win(Pattern::Diagonals),
+            Win {
+                board: 0,
+                turn: 3,
+                number: 5,
+                line: Line::Diagonal,
+                score: 5 * (2 + 4 + 6)
+            }
+        );
+        assert_eq!(win(Pattern::Full).score, 0);
+        let mask = Pattern::Mask(vec![Point::new(1, 0), Point::new(0, 1)]);
+        assert_eq!(win(mask).number, 2);
+        assert!(bingo
+            .play(&[Pattern::Mask(vec![Point::new(2, 0)])])
+            .is_empty());
+
+        let bingo: Bingo = "1,2,3\n\n1 2\n3 4\n5 6\n\n7 8\n9 1\n2 3\n".parse().unwrap();
+        assert_eq!((bingo.boards.len(), bingo.boards[1].rows()), (2, 3));
+        let err = "1,2\n\n1 2\n3 4\n\n5 6\n".parse::<Bingo>().unwrap_err();
+        assert_eq!((err.line, err.message.as_str()), (6, "Incomplete board"));
+    }
+
+    #[test]
+    fn day4_events() {
+        let bingo = load_test_data();
+        let mut events = Vec::new();
+        bingo.replay(&[Pattern::Rows, Pattern::Columns], |event| {
+            events.push(event)
+        });
+        assert_eq!(events[0], Event::Called { turn: 1, number: 7 });
+        assert_eq!(
+            events[3],
+            Event::Marked {
+                turn: 1,
+                board: 2,
+                number: 7,
+                cell: Point::new(4, 4)
+            }
+        );
+        assert_eq!(events.last().unwrap().turn(), 15);
+
+        let json: Vec<serde_json::Value> = events.iter().map(Event::to_json).collect();
+        let won = json.iter().find(|event| event["event"] == "won").unwrap();
+        assert_eq!(won["board"], 2);
+        assert_eq!(won["line"], "row 0");
+        assert_eq!(won["score"], 4512);
+        assert!(json.iter().all(|event| !event.to_string().contains('\n')));
+
+        let mut replay = Replay::new(&bingo);
+        for event in events.iter().take_while(|event| event.turn() <= 12) {
+            replay.apply(event);
+        }
+        let frame = replay.render(80);
+        assert!(frame.starts_with("Turn 12: called 24\n"));
+        assert!(frame.contains("\x1b[1;32;7m14\x1b[0m \x1b[1;32;7m21\x1b[0m"));
+        assert!(frame.contains("22 13 \x1b[7m17\x1b[0m \x1b[7m11\x1b[0m \x1b[7m 0\x1b[0m"));
+        assert!(frame.ends_with("\nBoard 2 won with row 0 on turn 12, scoring 4512\n"));
+    }
+
+    /// Plays every board by scanning its grid for a line of `patterns` with every cell marked
+    /// after each number, as `(board, turn, number, line, score)` of each win.
+    fn naive_wins(bingo: &Bingo, patterns: &[Pattern]) -> Vec<(usize, usize, i32, Line, i32)> {
+        let mut grids: Vec<Vec<Vec<(bool, i32)>>> = bingo
+            .boards
+            .iter()
+            .map(|board| {
+                (0..board.rows())
+                    .map(|row| {
+                        (0..board.cols())
+                            .map(|col| board.cells[Point::new(row, col)])
+                            .collect()
+                    })
+                    .collect()
+            })
+            .collect();
+        let mut won = vec![false; grids.len()];
+        let mut wins = Vec::new();
+        for (turn, &number) in bingo.rolls.iter().enumerate() {
+            for (board, grid) in grids.iter_mut().enumerate() {
+                if won[board] {
+                    continue;
+                }
+                for cell in grid.iter_mut().flatten() {
+                    if cell.1 == number {
+                        cell.0 = true;
+                    }
+                }
+
+                let (rows, cols) = (grid.len(), grid[0].len());
+                let mut lines: Vec<(Line, Vec<(usize, usize)>)> = Vec::new();
+                for pattern in patterns {
+                    match pattern {
+                        Pattern::Rows => {
+                            for i in 0..rows {
+                                lines.push((Line::Row(i), (0..cols).map(|col| (i, col)).collect()));
+                            }
+                        }
+                        Pattern::Columns => {
+                            for i in 0..cols {
+                                lines.push((
+                                    Line::Column(i),
+                                    (0..rows).map(|row| (row, i)).collect(),
+                                ));
+                            }
+                        }
+                        Pattern::Diagonals => {
+                            let side = rows.min(cols);
+                            lines.push((Line::Diagonal, (0..side).map(|i| (i, i)).collect()));
+                            lines.push((
+                                Line::AntiDiagonal,
+                                (0..side).map(|i| (i, cols - 1 - i)).collect(),
+                            ));
+                        }
+                        Pattern::Corners => lines.push((
+                            Line::Corners,
+                            vec![(0, 0), (0, cols - 1), (rows - 1, 0), (rows - 1, cols - 1)],
+                        )),
+                        Pattern::Full => lines.push((
+                            Line::Full,
+                            (0..rows)
+                                .flat_map(|row| (0..cols).map(move |col| (row, col)))
+                                .collect(),
+                        )),
+                        Pattern::Mask(cells) => lines.push((
+                            Line::Mask(cells.clone()),
+                            cells.iter().map(|cell| (cell.row, cell.col)).collect(),
+                        )),
+                    }
+                }
+                let is_marked = |&(row, col): &(usize, usize)| {
+                    grid.get(row)
+                        .and_then(|cells| cells.get(col))
+                        .is_some_and(|cell| cell.0)
+                };
+                let line = lines
+                    .into_iter()
+                    .find(|(_line, cells)| !cells.is_empty() && cells.iter().all(is_marked))
+                    .map(|(line, _cells)| line);
+                if let Some(line) = line {
+                    won[board] = true;
+                    let unmarked: i32 = grid
+                        .iter()
+                        .flatten()
+                        .filter(|(marked, _)| !marked)
+                        .map(|(_, number)| number)
+                        .sum();
+                    wins.push((board, turn + 1, number, line, number * unmarked));
+                }
+            }
+        }
+        wins
+    }
+
+    /// Games of boards from 1×1 to 6×6, each with distinct numbers, and numbers called any number
+    /// of times.
+    fn games() -> impl Strategy<Value = Bingo> {
+        let board = (1..=6usize, 1..=6usize).prop_flat_map(|(rows, cols)| {
+            Just((0..100).collect::<Vec<i32>>())
+                .prop_shuffle()
+                .prop_map(move |numbers| {
+                    numbers[..rows * cols]
+                        .chunks(cols)
+                        .map(<[i32]>::to_vec)
+                        .collect::<Vec<_>>()
+                })
+        });
+        (
+            prop::collection::vec(board, 1..30),
+            prop::collection::vec(0..100i32, 0..150),
+        )
+            .prop_map(|(boards, rolls)| Bingo {
+                rolls,
+                boards: boards
+                    .into_iter()
+                    .map(|rows| Board::from_rows(rows).unwrap())
+                    .collect(),
+            })
+    }
+
+    /// A few patterns in any order, with masks of up to 3 cells that may lie outside the board.
+    fn patterns() -> impl Strategy<Value = Vec<Pattern>> {
+        let cell = (0..7usize, 0..7usize).prop_map(|(row, col)| Point::new(row, col));
+        let pattern = prop_oneof![
+            Just(Pattern::Rows),
+            Just(Pattern::Columns),
+            Just(Pattern::Diagonals),
+            Just(Pattern::Corners),
+            Just(Pattern::Full),
+            prop::collection::vec(cell, 0..4).prop_map(Pattern::Mask),
+        ];
+        prop::collection::vec(pattern, 1..4)
+    }
+
+    proptest! {
+        #[test]
+        fn day4_matches_naive(bingo in games(), patterns in patterns()) {
+            let wins: Vec<(usize, usize, i32, Line, i32)> = bingo
+                .play(&patterns)
+                .into_iter()
+                .map(|win| (win.board, win.turn, win.number, win.line, win.score))
+                .collect();
+            prop_assert_eq!(wins, naive_wins(&bingo, &patterns));
+        }
+    }
+}
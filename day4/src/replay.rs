@@ -0,0 +1,132 @@
+use grid::Point;
+
+use crate::{Bingo, Board, Event, Win};
+
+const MARKED: &str = "\x1b[7m";
+const WINNING: &str = "\x1b[1;32;7m";
+const RESET: &str = "\x1b[0m";
+const GAP: usize = 3;
+
+/// The boards of a game as they are after each [`Event`], drawn for a terminal.
+#[derive(Debug, Clone)]
+pub struct Replay {
+    boards: Vec<Board>,
+    wins: Vec<Option<Win>>,
+    /// the last number called, on which turn
+    called: Option<(usize, i32)>,
+}
+impl Replay {
+    pub fn new(bingo: &Bingo) -> Self {
+        Self {
+            boards: bingo.boards.clone(),
+            wins: vec![None; bingo.boards.len()],
+            called: None,
+        }
+    }
+    pub fn apply(&mut self, event: &Event) {
+        match event {
+            Event::Called { turn, number } => self.called = Some((*turn, *number)),
+            Event::Marked { board, number, .. } => {
+                self.boards[*board].mark(*number);
+            }
+            Event::Won(win) => self.wins[win.board] = Some(win.clone()),
+        }
+    }
+    /// Draws the boards side by side in rows up to `width` characters wide, with marked cells in
+    /// reverse video and the cells of winning lines in green, followed by the wins so far.
+    pub fn render(&self, width: usize) -> String {
+        let mut frame = match self.called {
+            Some((turn, number)) => format!("Turn {}: called {}\n", turn, number),
+            None => "Ready\n".to_string(),
+        };
+
+        let cell_width = self
+            .boards
+            .iter()
+            .flat_map(|board| board.cells.iter())
+            .map(|(_marked, number)| number.to_string().len())
+            .max()
+            .unwrap_or(1);
+        let rows = self.boards.iter().map(Board::rows).max().unwrap_or(0);
+        let board_width = self
+            .boards
+            .iter()
+            .map(|board| board.cols() * (cell_width + 1) - 1)
+            .chain(
+                self.boards
+                    .len()
+                    .checked_sub(1)
+                    .map(|last| format!("#{}", last).len()),
+            )
+            .max()
+            .unwrap_or(0);
+        let per_row = ((width + GAP) / (board_width + GAP)).max(1);
+
+        let indices: Vec<usize> = (0..self.boards.len()).collect();
+        for chunk in indices.chunks(per_row) {
+            frame += "\n";
+            let headers: Vec<String> = chunk.iter().map(|board| format!("#{}", board)).collect();
+            frame += &join(
+                headers.iter().map(|header| (header.clone(), header.len())),
+                board_width,
+            );
+            for row in 0..rows {
+                let lines = chunk.iter().map(|&board| self.row(board, row, cell_width));
+                frame += &join(lines, board_width);
+            }
+        }
+
+        let mut wins: Vec<&Win> = self.wins.iter().flatten().collect();
+        wins.sort_by_key(|win| (win.turn, win.board));
+        if !wins.is_empty() {
+            frame += "\n";
+        }
+        for win in wins {
+            frame += &format!(
+                "Board {} won with {} on turn {}, scoring {}\n",
+                win.board, win.line, win.turn, win.score
+            );
+        }
+        frame
+    }
+    /// The `row`th row of a board, with escape codes, and how wide it shows.
+    fn row(&self, board: usize, row: usize, cell_width: usize) -> (String, usize) {
+        let card = &self.boards[board];
+        if row >= card.rows() {
+            return (String::new(), 0);
+        }
+
+        let winning = self.wins[board]
+            .as_ref()
+            .map(|win| win.line.cells(card.rows(), card.cols()))
+            .unwrap_or_default();
+        let cells: Vec<String> = (0..card.cols())
+            .map(|col| {
+                let point = Point::new(row, col);
+                let (marked, number) = card.cells[point];
+                let number = format!("{:>width$}", number, width = cell_width);
+                if winning.contains(&point) {
+                    format!("{}{}{}", WINNING, number, RESET)
+                } else if marked {
+                    format!("{}{}{}", MARKED, number, RESET)
+                } else {
+                    number
+                }
+            })
+            .collect();
+        (cells.join(" "), card.cols() * (cell_width + 1) - 1)
+    }
+}
+
+/// Joins the `(text, shown width)` of each board into a line, padding each to `width`.
+fn join(parts: impl Iterator<Item = (String, usize)>, width: usize) -> String {
+    let parts: Vec<(String, usize)> = parts.collect();
+    let mut line = String::new();
+    for (i, (text, shown)) in parts.iter().enumerate() {
+        line += text;
+        if i + 1 < parts.len() {
+            line += &" ".repeat(width - shown + GAP);
+        }
+    }
+    line.trim_end().to_string() + "\n"
+}
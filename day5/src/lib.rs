@@ -0,0 +1,560 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    error::Error,
+    fmt,
+    str::FromStr,
+};
+
+use common::{parse_lines, parse_token, ParseError, Solver};
+
+mod segment;
+
+pub use segment::{Intersection, Segment};
+
+/// [Day 5: Hydrothermal Venture](https://adventofcode.com/2021/day/5)
+///
+/// ```
+/// let lines: Vec<day5::Segment> = include_str!("test.txt")
+///     .lines()
+///     .map(|line| line.parse().unwrap())
+///     .collect();
+/// assert_eq!(day5::first(&lines), 5);
+/// assert_eq!(day5::second(&lines), 12);
+/// ```
+pub struct Day5;
+impl Solver for Day5 {
+    const DAY: u8 = 5;
+    const INPUT: &'static str = include_str!("input.txt");
+
+    type Input<'a> = Vec<Segment>;
+    type First = i32;
+    type Second = i32;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_lines(input, str::parse)
+    }
+    fn first(input: &Self::Input<'_>) -> Result<Self::First, Box<dyn Error>> {
+        Ok(first(input))
+    }
+    fn second(input: &Self::Input<'_>) -> Result<Self::Second, Box<dyn Error>> {
+        Ok(second(input))
+    }
+}
+
+pub fn first(lines: &[Segment]) -> i32 {
+    overlaps(lines, &Orientation::AxisAligned) as i32
+}
+
+pub fn second(lines: &[Segment]) -> i32 {
+    overlaps(lines, &Orientation::All) as i32
+}
+
+/// The number of points covered by at least two of the lines `orientation` selects.
+pub fn overlaps(lines: &[Segment], orientation: &Orientation) -> usize {
+    VentMap::new(orientation.select(lines)).overlaps()
+}
+
+/// Which vent lines to count.
+pub enum Orientation {
+    /// horizontal and vertical lines, as in part 1
+    AxisAligned,
+    /// lines at 45 degrees
+    Diagonal,
+    /// every line, as in part 2
+    All,
+    /// the lines for which the predicate holds
+    Custom(Box<dyn Fn(&Segment) -> bool>),
+}
+impl Orientation {
+    pub fn matches(&self, line: &Segment) -> bool {
+        match self {
+            Orientation::AxisAligned => line.is_horizontal() || line.is_vertical(),
+            Orientation::Diagonal => line.is_diagonal(),
+            Orientation::All => true,
+            Orientation::Custom(predicate) => predicate(line),
+        }
+    }
+    /// The lines which match.
+    pub fn select<'a>(&'a self, lines: &'a [Segment]) -> impl Iterator<Item = &'a Segment> + 'a {
+        lines.iter().filter(move |line| self.matches(line))
+    }
+}
+impl fmt::Debug for Orientation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Orientation::AxisAligned => write!(f, "AxisAligned"),
+            Orientation::Diagonal => write!(f, "Diagonal"),
+            Orientation::All => write!(f, "All"),
+            Orientation::Custom(_predicate) => write!(f, "Custom(..)"),
+        }
+    }
+}
+impl FromStr for Orientation {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "axis" => Ok(Orientation::AxisAligned),
+            "diagonal" => Ok(Orientation::Diagonal),
+            "all" => Ok(Orientation::All),
+            _ => Err(format!("Invalid line orientation: {}", s)),
+        }
+    }
+}
+
+/// How many vent lines cover each point, counted in a buffer over the bounding box of the lines,
+/// or in a hash map when the box is too large for the points the lines cover.
+#[derive(Debug, Clone)]
+pub struct VentMap {
+    bounds: Option<Region>,
+    cells: Cells,
+}
+
+#[derive(Debug, Clone)]
+enum Cells {
+    Dense {
+        left: i32,
+        top: i32,
+        width: usize,
+        counts: Vec<u32>,
+    },
+    Sparse(HashMap<Coordinates, u32>),
+}
+
+/// A rectangle of points, including its edges.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Region {
+    pub left: i32,
+    pub top: i32,
+    pub right: i32,
+    pub bottom: i32,
+}
+impl Region {
+    /// The smallest region with both points as corners.
+    pub fn new(a: &Coordinates, b: &Coordinates) -> Self {
+        Self {
+            left: a.x.min(b.x),
+            top: a.y.min(b.y),
+            right: a.x.max(b.x),
+            bottom: a.y.max(b.y),
+        }
+    }
+    pub fn contains(&self, point: &Coordinates) -> bool {
+        (self.left..=self.right).contains(&point.x) && (self.top..=self.bottom).contains(&point.y)
+    }
+    pub fn width(&self) -> u64 {
+        (i64::from(self.right) - i64::from(self.left) + 1) as u64
+    }
+    pub fn height(&self) -> u64 {
+        (i64::from(self.bottom) - i64::from(self.top) + 1) as u64
+    }
+    fn intersection(&self, other: &Region) -> Option<Region> {
+        let region = Region {
+            left: self.left.max(other.left),
+            top: self.top.max(other.top),
+            right: self.right.min(other.right),
+            bottom: self.bottom.min(other.bottom),
+        };
+        (region.left <= region.right && region.top <= region.bottom).then_some(region)
+    }
+    fn points(&self) -> impl Iterator<Item = Coordinates> {
+        let (left, right) = (self.left, self.right);
+        (self.top..=self.bottom)
+            .flat_map(move |y| (left..=right).map(move |x| Coordinates { x, y }))
+    }
+}
+
+impl VentMap {
+    /// The most cells of the buffer per point covered before falling back to a hash map, to keep
+    /// the memory linear in the points covered.
+    const MAX_CELLS_PER_POINT: u64 = 8;
+
+    pub fn new<'a>(lines: impl IntoIterator<Item = &'a Segment>) -> Self {
+        let lines: Vec<&Segment> = lines.into_iter().collect();
+        let points: u64 = lines.iter().map(|line| line.point_count()).sum();
+        let bounds = lines
+            .iter()
+            .map(|line| Region::new(&line.start, &line.end))
+            .reduce(|a, b| Region {
+                left: a.left.min(b.left),
+                top: a.top.min(b.top),
+                right: a.right.max(b.right),
+                bottom: a.bottom.max(b.bottom),
+            });
+
+        let area = bounds.map_or(0, |bounds| bounds.width().saturating_mul(bounds.height()));
+        let cells = match bounds {
+            Some(bounds) if area <= (points * Self::MAX_CELLS_PER_POINT).max(1 << 20) => {
+                Cells::Dense {
+                    left: bounds.left,
+                    top: bounds.top,
+                    width: bounds.width() as usize,
+                    counts: vec![0; area as usize],
+                }
+            }
+            _ => Cells::Sparse(HashMap::new()),
+        };
+
+        let mut map = Self { bounds, cells };
+        for point in lines.iter().flat_map(|line| line.points()) {
+            map.add(point);
+        }
+        map
+    }
+    fn add(&mut self, point: Coordinates) {
+        match &mut self.cells {
+            Cells::Dense {
+                left,
+                top,
+                width,
+                counts,
+            } => {
+                let (col, row) = ((point.x - *left) as usize, (point.y - *top) as usize);
+                counts[row * *width + col] += 1;
+            }
+            Cells::Sparse(counts) => *counts.entry(point).or_insert(0) += 1,
+        }
+    }
+    /// The smallest region covering every line, or `None` without lines.
+    pub fn bounds(&self) -> Option<Region> {
+        self.bounds
+    }
+    /// The number of lines covering `point`.
+    pub fn get(&self, point: &Coordinates) -> u32 {
+        match &self.cells {
+            Cells::Dense {
+                left,
+                top,
+                width,
+                counts,
+            } => match self.bounds {
+                Some(bounds) if bounds.contains(point) => {
+                    let (col, row) = ((point.x - *left) as usize, (point.y - *top) as usize);
+                    counts[row * *width + col]
+                }
+                _ => 0,
+            },
+            Cells::Sparse(counts) => counts.get(point).copied().unwrap_or(0),
+        }
+    }
+    /// The points of `region` covered by at least one line and by how many, row by row.
+    pub fn region(&self, region: &Region) -> Vec<(Coordinates, u32)> {
+        match &self.cells {
+            Cells::Dense { .. } => self
+                .bounds
+                .and_then(|bounds| bounds.intersection(region))
+                .into_iter()
+                .flat_map(|region| region.points())
+                .map(|point| {
+                    let count = self.get(&point);
+                    (point, count)
+                })
+                .filter(|(_point, count)| *count > 0)
+                .collect(),
+            Cells::Sparse(counts) => {
+                let mut covered: Vec<(Coordinates, u32)> = counts
+                    .iter()
+                    .filter(|(point, _count)| region.contains(point))
+                    .map(|(point, count)| (point.clone(), *count))
+                    .collect();
+                covered.sort_by_key(|(point, _count)| (point.y, point.x));
+                covered
+            }
+        }
+    }
+    /// How many points are covered by each number of lines, leaving out the points not covered.
+    pub fn histogram(&self) -> BTreeMap<u32, usize> {
+        let mut histogram = BTreeMap::new();
+        for count in self.counts().filter(|count| *count > 0) {
+            *histogram.entry(count).or_insert(0) += 1;
+        }
+        histogram
+    }
+    /// The number of points covered by at least `threshold` lines.
+    pub fn at_least(&self, threshold: u32) -> usize {
+        self.counts().filter(|count| *count >= threshold).count()
+    }
+    /// The number of points covered by at least two lines.
+    pub fn overlaps(&self) -> usize {
+        self.at_least(2)
+    }
+    fn counts(&self) -> Box<dyn Iterator<Item = u32> + '_> {
+        match &self.cells {
+            Cells::Dense { counts, .. } => Box::new(counts.iter().copied()),
+            Cells::Sparse(counts) => Box::new(counts.values().copied()),
+        }
+    }
+    /// Draws `region` like the diagrams of the puzzle, with `.` for the points no line covers, the
+    /// number of lines covering the others, and `#` for ten or more.
+    pub fn to_ascii(&self, region: &Region) -> String {
+        let mut diagram = String::new();
+        for y in region.top..=region.bottom {
+            for x in region.left..=region.right {
+                diagram.push(match self.get(&Coordinates { x, y }) {
+                    0 => '.',
+                    count @ 1..=9 => char::from_digit(count, 10).expect("a single digit"),
+                    _ => '#',
+                });
+            }
+            diagram.push('\n');
+        }
+        diagram
+    }
+    /// Draws `region` as a binary grayscale PGM image, from black where no line passes to white
+    /// where the most lines of the region overlap.
+    pub fn to_pgm(&self, region: &Region) -> Vec<u8> {
+        let counts: Vec<u32> = region.points().map(|point| self.get(&point)).collect();
+        let most = counts.iter().copied().max().unwrap_or(0).max(1);
+        let mut image = format!("P5\n{} {}\n255\n", region.width(), region.height()).into_bytes();
+        image.extend(
+            counts
+                .iter()
+                .map(|count| (u64::from(*count) * 255 / u64::from(most)) as u8),
+        );
+        image
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Coordinates {
+    pub x: i32,
+    pub y: i32,
+}
+impl FromStr for Coordinates {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        const EXPECTED: &str = "coordinates such as `0,9`";
+
+        let (x, y) = s
+            .trim()
+            .split_once(',')
+            .ok_or_else(|| ParseError::new(s, s.trim(), "Missing `,`", EXPECTED))?;
+        let x = parse_token(s, x, "Error parsing x coordinate", EXPECTED)?;
+        let y = parse_token(s, y, "Error parsing y coordinate", EXPECTED)?;
+
+        Ok(Self { x, y })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use num_rational::Ratio;
+    use proptest::prelude::*;
+
+    use crate::{
+        first, overlaps, second, Coordinates, Intersection, Orientation, Region, Segment, VentMap,
+    };
+
+    fn load_test_data() -> Vec<Segment> {
+        include_str!("test.txt")
+            .lines()
+            .filter(|line| !line.is_empty())
+            .map(|line| line.parse().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn day5_first() {
+        let input = load_test_data();
+        assert_eq!(first(&input), 5);
+    }
+
+    #[test]
+    fn day5_second() {
+        let input = load_test_data();
+        assert_eq!(second(&input), 12);
+    }
+
+    #[test]
+    fn day5_orientation() {
+        let input = load_test_data();
+        assert_eq!(overlaps(&input, &Orientation::AxisAligned), 5);
+        assert_eq!(overlaps(&input, &Orientation::Diagonal), 4);
+        assert_eq!(overlaps(&input, &"all".parse().unwrap()), 12);
+        let long = Orientation::Custom(Box::new(|line| line.point_count() > 5));
+        assert_eq!(long.select(&input).count(), 4);
+        assert_eq!(overlaps(&input, &long), 1);
+    }
+
+    /// Horizontal, vertical and diagonal lines, spread apart enough by `spread` 10000 that the map
+    /// counts them in a hash map.
+    fn vents() -> impl Strategy<Value = Vec<Segment>> {
+        let line = (0..1000i32, 0..1000i32, 0..4usize, 0..20i32);
+        (
+            prop::collection::vec(line, 1..200),
+            prop::sample::select(vec![1, 10_000]),
+        )
+            .prop_map(|(lines, spread)| {
+                lines
+                    .into_iter()
+                    .map(|(x, y, direction, length)| {
+                        let (x, y) = (x * spread, y * spread);
+                        let (dx, dy) = [(1, 0), (0, 1), (1, 1), (1, -1)][direction];
+                        let end = Coordinates {
+                            x: x + dx * length,
+                            y: y + dy * length,
+                        };
+                        Segment::new(Coordinates { x, y }, end)
+                    })
+                    .collect()
+            })
+    }
+
+    /// Counts the points of each line one step at a time.
+    fn naive_counts(lines: &[Segment]) -> HashMap<Coordinates, u32> {
+        let mut counts = HashMap::new();
+        for Segment { start, end } in lines {
+            let (dx, dy) = ((end.x - start.x).signum(), (end.y - start.y).signum());
+            let mut point = start.clone();
+            loop {
+                *counts.entry(point.clone()).or_insert(0) += 1;
+                if point == *end {
+                    break;
+                }
+                point = Coordinates {
+                    x: point.x + dx,
+                    y: point.y + dy,
+                };
+            }
+        }
+        counts
+    }
+
+    #[test]
+    fn day5_far_apart() {
+        let lines: Vec<Segment> = [
+            "-2000000000,0 -> -2000000000,2",
+            "2000000000,1 -> 1999999999,1",
+        ]
+        .iter()
+        .map(|line| line.parse().unwrap())
+        .collect();
+        assert_eq!(VentMap::new(&lines).overlaps(), 0);
+        assert_eq!(
+            lines[1].points().collect::<Vec<_>>(),
+            vec![
+                Coordinates {
+                    x: 2000000000,
+                    y: 1
+                },
+                Coordinates {
+                    x: 1999999999,
+                    y: 1
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn day5_queries() {
+        let map = VentMap::new(&load_test_data());
+        let bounds = map.bounds().unwrap();
+        assert_eq!(
+            map.to_ascii(&bounds),
+            "1.1....11.
+.111...2..
+..2.1.111.
+...1.2.2..
+.112313211
+...1.2....
+..1...1...
+.1.....1..
+1.......1.
+222111....
+"
+        );
+        assert_eq!(map.get(&Coordinates { x: 4, y: 4 }), 3);
+        assert_eq!(map.get(&Coordinates { x: 10, y: 4 }), 0);
+        assert_eq!(map.at_least(3), 2);
+        assert_eq!(
+            map.histogram().into_iter().collect::<Vec<_>>(),
+            vec![(1, 27), (2, 10), (3, 2)]
+        );
+
+        let region = Region::new(&Coordinates { x: 5, y: 3 }, &Coordinates { x: 3, y: 4 });
+        let covered: Vec<(i32, i32, u32)> = map
+            .region(&region)
+            .into_iter()
+            .map(|(point, count)| (point.x, point.y, count))
+            .collect();
+        assert_eq!(
+            covered,
+            vec![(3, 3, 1), (5, 3, 2), (3, 4, 2), (4, 4, 3), (5, 4, 1)]
+        );
+
+        let pgm = map.to_pgm(&region);
+        assert_eq!(pgm, b"P5\n3 2\n255\n\x55\x00\xaa\xaa\xff\x55");
+    }
+
+    #[test]
+    fn day5_segments() {
+        let segment = |line: &str| line.parse::<Segment>().unwrap();
+        let points: Vec<(i32, i32)> = segment("6,4 -> 0,0")
+            .points()
+            .map(|point| (point.x, point.y))
+            .collect();
+        assert_eq!(points, vec![(6, 4), (3, 2), (0, 0)]);
+        assert_eq!(segment("1,1 -> 1,1").point_count(), 1);
+        assert!(segment("0,4 -> 4,0").is_diagonal() && !segment("0,0 -> 2,1").is_diagonal());
+
+        let point = |x: (i128, i128), y: (i128, i128)| {
+            Some(Intersection::Point {
+                x: Ratio::new(x.0, x.1),
+                y: Ratio::new(y.0, y.1),
+            })
+        };
+        let crossing = segment("0,0 -> 2,1").intersection(&segment("0,1 -> 2,0"));
+        assert_eq!(crossing, point((1, 1), (1, 2)));
+        let touching = segment("0,0 -> 3,3").intersection(&segment("3,3 -> 5,0"));
+        assert_eq!(touching, point((3, 1), (3, 1)));
+        assert_eq!(
+            segment("0,0 -> 2,1").intersection(&segment("3,0 -> 5,1")),
+            None
+        );
+        assert_eq!(
+            segment("0,0 -> 1,5").intersection(&segment("2,0 -> 3,1")),
+            None
+        );
+        assert_eq!(
+            segment("0,0 -> 6,3").intersection(&segment("8,4 -> 2,1")),
+            Some(Intersection::Overlap(segment("2,1 -> 6,3")))
+        );
+        assert_eq!(
+            segment("0,0 -> 2,2").intersection(&segment("2,2 -> 4,4")),
+            point((2, 1), (2, 1))
+        );
+        assert_eq!(
+            segment("0,0 -> 2,2").intersection(&segment("3,3 -> 4,4")),
+            None
+        );
+        assert_eq!(
+            segment("1,2 -> 1,2").intersection(&segment("0,0 -> 2,4")),
+            point((1, 1), (2, 1))
+        );
+    }
+
+    #[test]
+    fn day5_parse_errors() {
+        let err = "0,9 -> 5,x".parse::<Segment>().unwrap_err();
+        assert_eq!((err.column, err.text.as_str()), (10, "x"));
+        let err = "0,9 5,9".parse::<Segment>().unwrap_err();
+        assert_eq!((err.column, err.message.as_str()), (8, "Missing `->`"));
+        let err = "0,9 -> 59".parse::<Segment>().unwrap_err();
+        assert_eq!((err.column, err.message.as_str()), (8, "Missing `,`"));
+    }
+
+    proptest! {
+        #[test]
+        fn day5_matches_naive(lines in vents()) {
+            let map = VentMap::new(&lines);
+            let naive = naive_counts(&lines);
+            prop_assert_eq!(map.overlaps(), naive.values().filter(|count| **count >= 2).count());
+            for (point, count) in &naive {
+                prop_assert_eq!(map.get(point), *count);
+            }
+        }
+    }
+}
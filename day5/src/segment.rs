@@ -0,0 +1,197 @@
+use std::{cmp::Ordering, str::FromStr};
+
+use common::ParseError;
+use num_rational::Ratio;
+
+use crate::Coordinates;
+
+/// A line of vents between two points with integer coordinates, at any angle.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Segment {
+    pub start: Coordinates,
+    pub end: Coordinates,
+}
+
+/// Where two segments meet.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Intersection {
+    /// a single point, which may lie between lattice points
+    Point { x: Ratio<i128>, y: Ratio<i128> },
+    /// the part the segments share when they lie on the same line, from the start of the first
+    Overlap(Segment),
+}
+
+impl Intersection {
+    fn at(point: &Coordinates) -> Self {
+        Intersection::Point {
+            x: ratio(point.x),
+            y: ratio(point.y),
+        }
+    }
+}
+
+impl Segment {
+    pub fn new(start: Coordinates, end: Coordinates) -> Self {
+        Self { start, end }
+    }
+    pub fn is_horizontal(&self) -> bool {
+        self.start.y == self.end.y
+    }
+    pub fn is_vertical(&self) -> bool {
+        self.start.x == self.end.x
+    }
+    /// Whether the segment is at 45 degrees, like the diagonal lines of the puzzle.
+    pub fn is_diagonal(&self) -> bool {
+        let (dx, dy) = self.delta();
+        dx != 0 && dx.abs() == dy.abs()
+    }
+    /// The number of lattice points on the segment, including both ends.
+    pub fn point_count(&self) -> u64 {
+        let (dx, dy) = self.delta();
+        gcd(dx.unsigned_abs(), dy.unsigned_abs()) + 1
+    }
+    /// Every lattice point on the segment, from `start` to `end`, stepping by the direction of the
+    /// segment divided by the gcd of its components.
+    pub fn points(&self) -> impl Iterator<Item = Coordinates> {
+        let (dx, dy) = self.delta();
+        let steps = self.point_count() - 1;
+        let (dx, dy) = match steps {
+            0 => (0, 0),
+            _ => (dx / steps as i64, dy / steps as i64),
+        };
+        let (x, y) = (i64::from(self.start.x), i64::from(self.start.y));
+        (0..=steps as i64).map(move |i| Coordinates {
+            x: (x + dx * i) as i32,
+            y: (y + dy * i) as i32,
+        })
+    }
+    pub fn contains(&self, point: &Coordinates) -> bool {
+        let (dx, dy) = self.delta();
+        let (px, py) = (
+            i64::from(point.x) - i64::from(self.start.x),
+            i64::from(point.y) - i64::from(self.start.y),
+        );
+        let within = |value: i32, a: i32, b: i32| a.min(b) <= value && value <= a.max(b);
+        cross((dx, dy), (px, py)) == 0
+            && within(point.x, self.start.x, self.end.x)
+            && within(point.y, self.start.y, self.end.y)
+    }
+    /// Where the segments meet, computed exactly.
+    pub fn intersection(&self, other: &Segment) -> Option<Intersection> {
+        let r = self.delta();
+        let s = other.delta();
+        let qp = (
+            i64::from(other.start.x) - i64::from(self.start.x),
+            i64::from(other.start.y) - i64::from(self.start.y),
+        );
+
+        if r == (0, 0) {
+            return other
+                .contains(&self.start)
+                .then(|| Intersection::at(&self.start));
+        }
+        if s == (0, 0) {
+            return self
+                .contains(&other.start)
+                .then(|| Intersection::at(&other.start));
+        }
+
+        let denominator = cross(r, s);
+        if denominator == 0 {
+            if cross(qp, r) != 0 {
+                // parallel
+                return None;
+            }
+            return self.overlap(other);
+        }
+
+        // self.start + t * r == other.start + u * s, with both t and u between 0 and 1
+        let t = Ratio::new(cross(qp, s), denominator);
+        let u = Ratio::new(cross(qp, r), denominator);
+        let unit = Ratio::from(0)..=Ratio::from(1);
+        if !unit.contains(&t) || !unit.contains(&u) {
+            return None;
+        }
+        Some(Intersection::Point {
+            x: ratio(self.start.x) + t * i128::from(r.0),
+            y: ratio(self.start.y) + t * i128::from(r.1),
+        })
+    }
+    /// The shared part of two segments on the same line.
+    fn overlap(&self, other: &Segment) -> Option<Intersection> {
+        // positions along `self`, with its start at 0 and its end at `dot(r, r)`
+        let r = self.delta();
+        let along = |point: &Coordinates| {
+            let offset = (
+                i64::from(point.x) - i64::from(self.start.x),
+                i64::from(point.y) - i64::from(self.start.y),
+            );
+            i128::from(offset.0) * i128::from(r.0) + i128::from(offset.1) * i128::from(r.1)
+        };
+        let mut ends = [
+            (0, &self.start),
+            (along(&self.end), &self.end),
+            (along(&other.start), &other.start),
+            (along(&other.end), &other.end),
+        ];
+        ends[2..].sort_by_key(|(along, _point)| *along);
+
+        let start = if ends[2].0 > ends[0].0 {
+            ends[2]
+        } else {
+            ends[0]
+        };
+        let end = if ends[3].0 < ends[1].0 {
+            ends[3]
+        } else {
+            ends[1]
+        };
+        match start.0.cmp(&end.0) {
+            Ordering::Greater => None,
+            Ordering::Equal => Some(Intersection::at(start.1)),
+            Ordering::Less => Some(Intersection::Overlap(Segment::new(
+                start.1.clone(),
+                end.1.clone(),
+            ))),
+        }
+    }
+    fn delta(&self) -> (i64, i64) {
+        (
+            i64::from(self.end.x) - i64::from(self.start.x),
+            i64::from(self.end.y) - i64::from(self.start.y),
+        )
+    }
+}
+impl FromStr for Segment {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        const EXPECTED: &str = "`<x1>,<y1> -> <x2>,<y2>`";
+
+        let (start, end) = s
+            .split_once("->")
+            .ok_or_else(|| ParseError::missing(s, "Missing `->`", EXPECTED))?;
+        let start: Coordinates = start
+            .parse()
+            .map_err(|err: ParseError| err.within(s, start))?;
+        let end: Coordinates = end.parse().map_err(|err: ParseError| err.within(s, end))?;
+
+        Ok(Self::new(start, end))
+    }
+}
+
+fn ratio(value: i32) -> Ratio<i128> {
+    Ratio::from(i128::from(value))
+}
+
+fn cross(a: (i64, i64), b: (i64, i64)) -> i128 {
+    i128::from(a.0) * i128::from(b.1) - i128::from(a.1) * i128::from(b.0)
+}
+
+fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
@@ -0,0 +1,270 @@
+use std::{error::Error, str::FromStr};
+
+use common::{parse_token, ParseError, Solver};
+use num_bigint::BigUint;
+
+/// [Day 6: Lanternfish](https://adventofcode.com/2021/day/6)
+///
+/// ```
+/// let fishes: day6::Langernfishes = "3,4,3,1,2".parse().unwrap();
+/// assert_eq!(day6::first(&fishes), 5934u32.into());
+/// assert_eq!(day6::second(&fishes), 26984457539u64.into());
+/// ```
+pub struct Day6;
+impl Solver for Day6 {
+    const DAY: u8 = 6;
+    const INPUT: &'static str = include_str!("input.txt");
+
+    type Input<'a> = Langernfishes;
+    type First = BigUint;
+    type Second = BigUint;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        input.parse()
+    }
+    fn first(input: &Self::Input<'_>) -> Result<Self::First, Box<dyn Error>> {
+        Ok(first(input))
+    }
+    fn second(input: &Self::Input<'_>) -> Result<Self::Second, Box<dyn Error>> {
+        Ok(second(input))
+    }
+}
+
+pub fn first(initial_state: &Langernfishes) -> BigUint {
+    population(initial_state, 80)
+}
+
+pub fn second(initial_state: &Langernfishes) -> BigUint {
+    population(initial_state, 256)
+}
+
+/// The number of lanternfish after `days`, in O(log `days`) 9×9 matrix products.
+pub fn population(fishes: &Langernfishes, days: u64) -> BigUint {
+    count(fishes, days, &BigUint::from(0u8))
+}
+
+/// The number of lanternfish after `days`, modulo `modulus`, without numbers beyond `u128`.
+///
+/// Panics if `modulus` is 0.
+pub fn population_modulo(fishes: &Langernfishes, days: u64, modulus: u64) -> u64 {
+    assert!(modulus > 0, "the modulus must be positive");
+    let zero = Modular { value: 0, modulus };
+    count(fishes, days, &zero).value
+}
+
+/// The sum of the numbers of fish with each timer after `days`: the sum of the columns of the
+/// transition matrix to the power of `days`, weighted by the numbers of fish at the start.
+fn count<T: Count>(fishes: &Langernfishes, days: u64, zero: &T) -> T {
+    let transition = Transition::day(zero).power(days, zero);
+    let mut total = zero.clone();
+    for (timer, fishes) in fishes.counts().iter().enumerate() {
+        for row in &transition.0 {
+            total = total.add(&row[timer].mul(&zero.of(*fishes)));
+        }
+    }
+    total
+}
+
+/// The numbers the population is counted in, with the zero of a kind giving the others.
+trait Count: Clone {
+    /// `value` counted in the same kind of number as `self`.
+    fn of(&self, value: u64) -> Self;
+    fn add(&self, other: &Self) -> Self;
+    fn mul(&self, other: &Self) -> Self;
+}
+impl Count for BigUint {
+    fn of(&self, value: u64) -> Self {
+        BigUint::from(value)
+    }
+    fn add(&self, other: &Self) -> Self {
+        self + other
+    }
+    fn mul(&self, other: &Self) -> Self {
+        self * other
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Modular {
+    value: u64,
+    modulus: u64,
+}
+impl Count for Modular {
+    fn of(&self, value: u64) -> Self {
+        Modular {
+            value: value % self.modulus,
+            modulus: self.modulus,
+        }
+    }
+    fn add(&self, other: &Self) -> Self {
+        let sum = (u128::from(self.value) + u128::from(other.value)) % u128::from(self.modulus);
+        self.of(sum as u64)
+    }
+    fn mul(&self, other: &Self) -> Self {
+        let product = u128::from(self.value) * u128::from(other.value) % u128::from(self.modulus);
+        self.of(product as u64)
+    }
+}
+
+/// How many fish with each timer (the rows) come from a fish with each timer (the columns).
+#[derive(Debug, Clone)]
+struct Transition<T>([[T; 9]; 9]);
+impl<T: Count> Transition<T> {
+    fn filled(zero: &T, is_one: impl Fn(usize, usize) -> bool) -> Self {
+        Self(std::array::from_fn(|row| {
+            std::array::from_fn(|col| zero.of(u64::from(is_one(row, col))))
+        }))
+    }
+    fn identity(zero: &T) -> Self {
+        Self::filled(zero, |row, col| row == col)
+    }
+    /// One day: every timer counts down, and a fish at 0 restarts at 6 and spawns one at 8.
+    fn day(zero: &T) -> Self {
+        Self::filled(zero, |row, col| {
+            col == row + 1 || (col == 0 && (row == 6 || row == 8))
+        })
+    }
+    fn mul(&self, other: &Self, zero: &T) -> Self {
+        Self(std::array::from_fn(|row| {
+            std::array::from_fn(|col| {
+                (0..9).fold(zero.clone(), |sum, k| {
+                    sum.add(&self.0[row][k].mul(&other.0[k][col]))
+                })
+            })
+        }))
+    }
+    /// By repeated squaring.
+    fn power(&self, mut exponent: u64, zero: &T) -> Self {
+        let mut result = Self::identity(zero);
+        let mut square = self.clone();
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result = result.mul(&square, zero);
+            }
+            exponent >>= 1;
+            if exponent > 0 {
+                square = square.mul(&square, zero);
+            }
+        }
+        result
+    }
+}
+
+/// The timers of the lanternfish, from 0 to 8.
+#[derive(Debug, Clone)]
+pub struct Langernfishes(Vec<u8>);
+impl Langernfishes {
+    /// How many fish have each timer.
+    pub fn counts(&self) -> [u64; 9] {
+        let mut counts = [0; 9];
+        for fish in &self.0 {
+            counts[usize::from(*fish)] += 1;
+        }
+        counts
+    }
+}
+impl FromStr for Langernfishes {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        const EXPECTED: &str = "comma separated timers from 0 to 8 such as `3,4,3,1,2`";
+
+        Ok(Self(
+            s.trim()
+                .split(',')
+                .map(|num| {
+                    let timer: u8 = parse_token(s, num, "Invalid number", EXPECTED)?;
+                    if timer > 8 {
+                        return Err(ParseError::new(s, num, "Invalid timer", EXPECTED));
+                    }
+                    Ok(timer)
+                })
+                .collect::<Result<Vec<_>, _>>()?,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use crate::{first, population, population_modulo, second, Langernfishes};
+    use num_bigint::BigUint;
+
+    /// Simulates every lanternfish one by one.
+    fn naive_count(mut fishes: Vec<u8>, days: i32) -> u64 {
+        for _ in 0..days {
+            let mut born = 0;
+            for fish in &mut fishes {
+                if *fish == 0 {
+                    *fish = 6;
+                    born += 1;
+                } else {
+                    *fish -= 1;
+                }
+            }
+            fishes.extend(std::iter::repeat_n(8, born));
+        }
+        fishes.len() as u64
+    }
+
+    fn timers() -> impl Strategy<Value = String> {
+        prop::collection::vec(0..=8i32, 1..8).prop_map(|timers| {
+            timers
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(",")
+        })
+    }
+
+    fn load_test_data() -> Langernfishes {
+        include_str!("test.txt").parse::<Langernfishes>().unwrap()
+    }
+
+    #[test]
+    fn day6_first() {
+        let input = load_test_data();
+        assert_eq!(first(&input), 5934u32.into());
+    }
+
+    #[test]
+    fn day6_second() {
+        let input = load_test_data();
+        assert_eq!(second(&input), 26984457539u64.into());
+    }
+
+    #[test]
+    fn day6_parse_errors() {
+        assert!("3,4,3,1,2\n".parse::<Langernfishes>().is_ok());
+        let err = "\n3,4,x".parse::<Langernfishes>().unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (2, 5, "x"));
+        let err = "3,9,3".parse::<Langernfishes>().unwrap_err();
+        assert_eq!((err.column, err.message.as_str()), (3, "Invalid timer"));
+    }
+
+    #[test]
+    fn day6_huge_days() {
+        let input = load_test_data();
+        assert_eq!(population(&input, 0), BigUint::from(5u8));
+        let days = 5000;
+        let modulus = 1_000_000_007;
+        assert_eq!(
+            BigUint::from(population_modulo(&input, days, modulus)),
+            population(&input, days) % modulus
+        );
+
+        // the population follows the characteristic polynomial of the transition, x^9 = x^2 + 1
+        let days = 1_000_000_000_000;
+        let at = |days| population_modulo(&input, days, modulus);
+        assert_eq!(at(days), (at(days - 7) + at(days - 9)) % modulus);
+    }
+
+    proptest! {
+        #[test]
+        fn day6_matches_naive(input in timers()) {
+            let fishes: Langernfishes = input.parse().unwrap();
+            prop_assert_eq!(first(&fishes), naive_count(fishes.0.clone(), 80).into());
+        }
+    }
+}
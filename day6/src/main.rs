@@ -1,90 +1,46 @@
-use std::{collections::HashMap, num::ParseIntError, str::FromStr};
-
-fn main() {
-    let input: Langernfishes = include_str!("input.txt").parse().unwrap();
-    println!("{}", first(&input));
-    println!("{}", second(&input));
-}
-
-fn first(initial_state: &Langernfishes) -> i64 {
-    const DAYS: i32 = 80;
-    let mut fishies: HashMap<i32, i64> = HashMap::new();
-    for fish in &initial_state.0 {
-        *fishies.entry(*fish).or_default() += 1;
-    }
-
-    for _ in 0..DAYS {
-        let mut new_map = HashMap::new();
-        for (timer, count) in fishies.into_iter() {
-            if timer == 0 {
-                *new_map.entry(8).or_default() += count;
-                *new_map.entry(6).or_default() += count;
-            } else {
-                *new_map.entry(timer - 1).or_default() += count;
-            }
+use std::{env, process};
+
+use common::Solver;
+use day6::{population, population_modulo, Day6};
+
+const USAGE: &str = "Usage: day6 [--days <n>] [--modulo <m>] [input]
+
+--days <n>      count the lanternfish after n days instead of answering both parts
+--modulo <m>    count the lanternfish modulo m, for days far beyond the parts";
+
+fn main() -> Result<(), common::Report> {
+    let mut days = None;
+    let mut modulus = None;
+    let mut path = None;
+
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--days" => match args.next().map(|n| n.parse()) {
+                Some(Ok(n)) => days = Some(n),
+                _ => usage("Expected a number of days"),
+            },
+            "--modulo" => match args.next().map(|m| m.parse()) {
+                Some(Ok(m)) if m > 0 => modulus = Some(m),
+                _ => usage("Expected a positive modulus"),
+            },
+            _ if path.is_none() => path = Some(arg),
+            _ => usage(&format!("Unexpected argument: {}", arg)),
         }
-
-        fishies = new_map
     }
 
-    fishies.values().sum()
-}
-
-fn second(initial_state: &Langernfishes) -> i64 {
-    const DAYS: i32 = 256;
-    let mut fishies: HashMap<i32, i64> = HashMap::new();
-    for fish in &initial_state.0 {
-        *fishies.entry(*fish).or_default() += 1;
-    }
-
-    for _ in 0..DAYS {
-        let mut new_map = HashMap::new();
-        for (timer, count) in fishies.into_iter() {
-            if timer == 0 {
-                *new_map.entry(8).or_default() += count;
-                *new_map.entry(6).or_default() += count;
-            } else {
-                *new_map.entry(timer - 1).or_default() += count;
-            }
+    let input = common::read_input(path.as_deref(), Day6::INPUT)?;
+    let fishes = Day6::parse(&input)?;
+    for days in days.map_or(vec![80, 256], |days| vec![days]) {
+        match modulus {
+            Some(modulus) => println!("{}", population_modulo(&fishes, days, modulus)),
+            None => println!("{}", population(&fishes, days)),
         }
-
-        fishies = new_map
-    }
-
-    fishies.values().sum()
-}
-
-struct Langernfishes(Vec<i32>);
-impl FromStr for Langernfishes {
-    type Err = ParseIntError;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(Self(
-            s.trim()
-                .split(',')
-                .map(|num| num.parse())
-                .collect::<Result<Vec<_>, _>>()?,
-        ))
     }
+    Ok(())
 }
 
-#[cfg(test)]
-mod tests {
-    use crate::{first, second, Langernfishes};
-
-    fn load_test_data() -> Langernfishes {
-        include_str!("test.txt").parse::<Langernfishes>().unwrap()
-    }
-
-    #[test]
-    fn day6_first() {
-        let input = load_test_data();
-        assert_eq!(first(&input), 5934);
-    }
-
-    #[test]
-    fn day6_second() {
-        let input = load_test_data();
-        assert_eq!(second(&input), 26984457539);
-    }
+fn usage(err: &str) -> ! {
+    eprintln!("{}\n{}", err, USAGE);
+    process::exit(2);
 }
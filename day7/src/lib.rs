@@ -0,0 +1,163 @@
+use std::{error::Error, str::FromStr};
+
+use common::{parse_token, ParseError, Solver};
+
+/// [Day 7: The Treachery of Whales](https://adventofcode.com/2021/day/7)
+///
+/// ```
+/// let crabs: day7::Crabs = "16,1,2,0,4,2,7,1,2,14".parse().unwrap();
+/// assert_eq!(day7::first(&crabs), 37);
+/// assert_eq!(day7::second(&crabs), 168);
+/// ```
+pub struct Day7;
+impl Solver for Day7 {
+    const DAY: u8 = 7;
+    const INPUT: &'static str = include_str!("input.txt");
+
+    type Input<'a> = Crabs;
+    type First = i32;
+    type Second = i32;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        input.parse()
+    }
+    fn first(input: &Self::Input<'_>) -> Result<Self::First, Box<dyn Error>> {
+        Ok(first(input))
+    }
+    fn second(input: &Self::Input<'_>) -> Result<Self::Second, Box<dyn Error>> {
+        Ok(second(input))
+    }
+}
+
+pub fn first(initial_state: &Crabs) -> i32 {
+    let target = median(&initial_state.0);
+    initial_state.0.iter().map(|i| (i - target).abs()).sum()
+}
+
+fn median(list: &[i32]) -> i32 {
+    match list.len() {
+        0 => {
+            panic!("empty list")
+        }
+        1 => list[0],
+        n if n % 2 == 0 => {
+            let mid = n / 2 - 1;
+            (list[mid] + list[mid + 1]) / 2
+        }
+        n => {
+            let mid = n / 2;
+            list[mid]
+        }
+    }
+}
+
+pub fn second(initial_state: &Crabs) -> i32 {
+    let mean = mean(&initial_state.0);
+    let cost = |distance| distance * (distance + 1) / 2;
+
+    [mean - 1, mean, mean + 1]
+        .iter()
+        .map(|target| {
+            initial_state
+                .0
+                .iter()
+                .map(|i| cost((i - target).abs()))
+                .sum()
+        })
+        .min()
+        .unwrap()
+}
+
+fn mean(list: &[i32]) -> i32 {
+    let sum = list.iter().sum::<i32>();
+    let len = list.len() as i32;
+    let mean = sum / len;
+
+    if ((mean + 1) * len - sum).abs() > (mean * len - sum).abs() {
+        mean
+    } else {
+        mean + 1
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Crabs(Vec<i32>); // inner vec should be sorted
+impl Crabs {
+    pub fn new(mut crabs: Vec<i32>) -> Self {
+        crabs.sort_unstable();
+        Self(crabs)
+    }
+}
+impl FromStr for Crabs {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self::new(
+            s.trim()
+                .split(',')
+                .map(|num| {
+                    parse_token(
+                        s,
+                        num,
+                        "Invalid number",
+                        "comma separated positions such as `16,1,2,0,4`",
+                    )
+                })
+                .collect::<Result<Vec<_>, _>>()?,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use crate::{first, second, Crabs};
+
+    /// Tries every target between the leftmost and rightmost crab.
+    fn naive_cheapest(crabs: &Crabs, cost: impl Fn(i32) -> i32) -> i32 {
+        let (min, max) = (crabs.0[0], crabs.0[crabs.0.len() - 1]);
+        (min..=max)
+            .map(|target| crabs.0.iter().map(|crab| cost((crab - target).abs())).sum())
+            .min()
+            .unwrap()
+    }
+
+    fn positions() -> impl Strategy<Value = String> {
+        prop::collection::vec(0..2000i32, 1..50).prop_map(|positions| {
+            positions
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(",")
+        })
+    }
+
+    fn load_test_data() -> Crabs {
+        include_str!("test.txt").parse::<Crabs>().unwrap()
+    }
+
+    #[test]
+    fn day7_first() {
+        let input = load_test_data();
+        assert_eq!(first(&input), 37);
+    }
+
+    #[test]
+    fn day7_second() {
+        let input = load_test_data();
+        assert_eq!(second(&input), 168);
+    }
+
+    proptest! {
+        #[test]
+        fn day7_matches_naive(input in positions()) {
+            let crabs: Crabs = input.parse().unwrap();
+            prop_assert_eq!(first(&crabs), naive_cheapest(&crabs, |distance| distance));
+            prop_assert_eq!(
+                second(&crabs),
+                naive_cheapest(&crabs, |distance| distance * (distance + 1) / 2)
+            );
+        }
+    }
+}
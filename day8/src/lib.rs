@@ -0,0 +1,319 @@
+use std::{collections::HashMap, error::Error, fmt::Display, str::FromStr};
+
+use common::{parse_lines, ParseError, Solver};
+
+/// [Day 8: Seven Segment Search](https://adventofcode.com/2021/day/8)
+///
+/// ```
+/// let displays: Vec<day8::ClockDisplay> = include_str!("test.txt")
+///     .lines()
+///     .map(|line| line.parse().unwrap())
+///     .collect();
+/// assert_eq!(day8::first(&displays), 26);
+/// assert_eq!(day8::second(&displays), Ok(61229));
+/// ```
+pub struct Day8;
+impl Solver for Day8 {
+    const DAY: u8 = 8;
+    const INPUT: &'static str = include_str!("input.txt");
+
+    type Input<'a> = Vec<ClockDisplay>;
+    type First = i32;
+    type Second = i32;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_lines(input, str::parse)
+    }
+    fn first(input: &Self::Input<'_>) -> Result<Self::First, Box<dyn Error>> {
+        Ok(first(input))
+    }
+    fn second(input: &Self::Input<'_>) -> Result<Self::Second, Box<dyn Error>> {
+        Ok(second(input)?)
+    }
+}
+
+pub fn first(displays: &[ClockDisplay]) -> i32 {
+    displays
+        .iter()
+        .map(|display| {
+            display
+                .outputs
+                .iter()
+                .filter(|segments| {
+                    [
+                        2, // to display 1
+                        4, // to display 4
+                        3, // to display 7
+                        7, // to display 8
+                    ]
+                    .contains(&segments.len())
+                })
+                .count() as i32
+        })
+        .sum()
+}
+
+pub fn second(displays: &[ClockDisplay]) -> Result<i32, DecodeError> {
+    displays
+        .iter()
+        .enumerate()
+        .map(|(i, display)| display.value().ok_or(DecodeError { display: i + 1 }))
+        .sum()
+}
+
+/// A display whose patterns match no wiring of the segments, counting the displays from 1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodeError {
+    pub display: usize,
+}
+impl Display for DecodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Display {} matches no wiring of the segments",
+            self.display
+        )
+    }
+}
+impl Error for DecodeError {}
+
+struct CorrectedSignals(HashMap<char, Token>);
+impl CorrectedSignals {
+    fn new(mappings: &[(char, Token)]) -> Self {
+        let mut hm = HashMap::new();
+        for (chr, token) in mappings {
+            hm.insert(*chr, *token);
+        }
+        Self(hm)
+    }
+    fn decode(&self, q: &str) -> Option<i32> {
+        // output single number
+        let mut output = Segments::new();
+        for chr in q.chars() {
+            output.set(*self.0.get(&chr)?);
+        }
+        output.decode()
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Token {
+    Top,
+    TopLeft,
+    TopRight,
+    Middle,
+    BottomLeft,
+    BottomRight,
+    Bottom,
+}
+
+struct Segments {
+    top: bool,
+    top_left: bool,
+    top_right: bool,
+    middle: bool,
+    bottom_left: bool,
+    bottom_right: bool,
+    bottom: bool,
+}
+impl Segments {
+    fn new() -> Self {
+        Self {
+            top: false,
+            top_left: false,
+            top_right: false,
+            middle: false,
+            bottom_left: false,
+            bottom_right: false,
+            bottom: false,
+        }
+    }
+    fn set(&mut self, token: Token) {
+        match token {
+            Token::Top => self.top = true,
+            Token::TopLeft => self.top_left = true,
+            Token::TopRight => self.top_right = true,
+            Token::Middle => self.middle = true,
+            Token::BottomLeft => self.bottom_left = true,
+            Token::BottomRight => self.bottom_right = true,
+            Token::Bottom => self.bottom = true,
+        }
+    }
+    fn decode(&self) -> Option<i32> {
+        let Segments {
+            top,
+            top_left,
+            top_right,
+            middle,
+            bottom_left,
+            bottom_right,
+            bottom,
+        } = self;
+
+        match (
+            top,
+            top_left,
+            top_right,
+            middle,
+            bottom_left,
+            bottom_right,
+            bottom,
+        ) {
+            (true, true, true, false, true, true, true) => Some(0),
+            (false, false, true, false, false, true, false) => Some(1),
+            (true, false, true, true, true, false, true) => Some(2),
+            (true, false, true, true, false, true, true) => Some(3),
+            (false, true, true, true, false, true, false) => Some(4),
+            (true, true, false, true, false, true, true) => Some(5),
+            (true, true, false, true, true, true, true) => Some(6),
+            (true, false, true, false, false, true, false) => Some(7),
+            (true, true, true, true, true, true, true) => Some(8),
+            (true, true, true, true, false, true, true) => Some(9),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ClockDisplay {
+    signals: Vec<String>,
+    outputs: Vec<String>,
+}
+impl ClockDisplay {
+    /// The number on the display, or `None` if its patterns match no wiring of the segments.
+    fn value(&self) -> Option<i32> {
+        // segments -> display number
+        // 2 -> 1
+        // 3 -> 7
+        // 4 -> 4
+        // 5 -> 2 or 3 or 5
+        // 6 -> 0 or 6 or 9
+        // 7 -> 8
+
+        // segments by count
+        // top = 8
+        // top left = 6
+        // top right = 8
+        // middle = 7
+        // bottom left = 4
+        // bottom right = 9
+        // bottom = 7
+        let one_signal = self.signals.iter().find(|sig| sig.len() == 2)?;
+        let four_signal = self.signals.iter().find(|sig| sig.len() == 4)?;
+        let seven_signal = self.signals.iter().find(|sig| sig.len() == 3)?;
+
+        let mut signal_count = HashMap::new();
+        for sig in &self.signals {
+            for chr in sig.chars() {
+                *signal_count.entry(chr).or_insert(0) += 1;
+            }
+        }
+
+        let bottom_left =
+            signal_count
+                .iter()
+                .find_map(|(chr, count)| if *count == 4 { Some(*chr) } else { None })?;
+        let top_left = signal_count
+            .iter()
+            .find_map(|(chr, count)| if *count == 6 { Some(*chr) } else { None })?;
+        let bottom_right =
+            signal_count
+                .iter()
+                .find_map(|(chr, count)| if *count == 9 { Some(*chr) } else { None })?;
+        let top_right = one_signal.chars().find(|chr| *chr != bottom_right)?;
+        let top = seven_signal
+            .chars()
+            .find(|chr| !one_signal.contains(*chr))?;
+        let middle = four_signal
+            .chars()
+            .find(|chr| *chr != top_left && *chr != top_right && *chr != bottom_right)?;
+        let bottom = ('a'..='g').find(|chr| {
+            ![top, top_left, top_right, middle, bottom_left, bottom_right].contains(chr)
+        })?;
+
+        let fixed_signals = CorrectedSignals::new(&[
+            (top, Token::Top),
+            (top_left, Token::TopLeft),
+            (top_right, Token::TopRight),
+            (middle, Token::Middle),
+            (bottom_left, Token::BottomLeft),
+            (bottom_right, Token::BottomRight),
+            (bottom, Token::Bottom),
+        ]);
+
+        self.outputs.iter().try_fold(0, |acc, digit| {
+            Some(acc * 10 + fixed_signals.decode(digit)?)
+        })
+    }
+}
+impl FromStr for ClockDisplay {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        const EXPECTED: &str =
+            "10 signal patterns, `|` and 4 output patterns of the segments `a` to `g`";
+
+        fn parse_patterns(line: &str, part: &str, count: usize) -> Result<Vec<String>, ParseError> {
+            let is_valid = |pattern: &str| {
+                pattern.chars().all(|chr| ('a'..='g').contains(&chr))
+                    && (1..pattern.len()).all(|i| !pattern[i..].contains(&pattern[i - 1..i]))
+            };
+
+            let patterns: Vec<&str> = part.split_whitespace().collect();
+            if let Some(pattern) = patterns.iter().find(|pattern| !is_valid(pattern)) {
+                return Err(ParseError::new(line, pattern, "Invalid pattern", EXPECTED));
+            }
+            if patterns.len() != count {
+                let message = format!("Expected {} patterns, found {}", count, patterns.len());
+                return Err(ParseError::new(line, part.trim(), message, EXPECTED));
+            }
+
+            Ok(patterns.into_iter().map(ToString::to_string).collect())
+        }
+
+        let (signals, outputs) = s
+            .split_once('|')
+            .ok_or_else(|| ParseError::missing(s, "Missing `|`", EXPECTED))?;
+
+        Ok(Self {
+            signals: parse_patterns(s, signals, 10)?,
+            outputs: parse_patterns(s, outputs, 4)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{first, second, ClockDisplay, DecodeError};
+
+    fn load_test_data() -> Vec<ClockDisplay> {
+        include_str!("test.txt")
+            .lines()
+            .filter(|line| !line.is_empty())
+            .map(|line| line.parse::<ClockDisplay>().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn day8_first() {
+        let input = load_test_data();
+        assert_eq!(first(&input), 26);
+    }
+
+    #[test]
+    fn day8_second() {
+        let input = load_test_data();
+        assert_eq!(second(&input), Ok(61229));
+    }
+
+    #[test]
+    fn day8_undecodable() {
+        let mut input = load_test_data();
+        input.insert(1, "a ab abc abcd b c d e f g | a b c d".parse().unwrap());
+        assert_eq!(second(&input), Err(DecodeError { display: 2 }));
+        assert_eq!(
+            second(&input).unwrap_err().to_string(),
+            "Display 2 matches no wiring of the segments"
+        );
+    }
+}
@@ -0,0 +1,136 @@
+use std::{collections::VecDeque, error::Error, fmt::Display, str::FromStr};
+
+use common::{ParseError, Solver};
+use grid::{Grid, Point};
+
+/// [Day 9: Smoke Basin](https://adventofcode.com/2021/day/9)
+///
+/// ```
+/// let heights: day9::FloorHeights = include_str!("test.txt").parse().unwrap();
+/// assert_eq!(day9::first(&heights), 15);
+/// assert_eq!(day9::second(&heights), Ok(1134));
+/// ```
+pub struct Day9;
+impl Solver for Day9 {
+    const DAY: u8 = 9;
+    const INPUT: &'static str = include_str!("input.txt");
+
+    type Input<'a> = FloorHeights;
+    type First = i32;
+    type Second = i32;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        input.parse()
+    }
+    fn first(input: &Self::Input<'_>) -> Result<Self::First, Box<dyn Error>> {
+        Ok(first(input))
+    }
+    fn second(input: &Self::Input<'_>) -> Result<Self::Second, Box<dyn Error>> {
+        Ok(second(input)?)
+    }
+}
+
+pub fn first(heights: &FloorHeights) -> i32 {
+    heights
+        .low_points()
+        .iter()
+        .map(|point| i32::from(heights.0[*point]) + 1)
+        .sum()
+}
+
+pub fn second(heights: &FloorHeights) -> Result<i32, TooFewBasins> {
+    let mut basin_sizes: Vec<i32> = heights
+        .low_points()
+        .iter()
+        .map(|point| heights.basin_size_at(*point))
+        .collect();
+
+    basin_sizes.sort_unstable();
+    basin_sizes.reverse();
+
+    match basin_sizes[..] {
+        [first, second, third, ..] => Ok(first * second * third),
+        _ => Err(TooFewBasins(basin_sizes.len())),
+    }
+}
+
+/// The floor has fewer than the three basins to multiply the sizes of.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TooFewBasins(pub usize);
+impl Display for TooFewBasins {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Found {} basins, expected at least 3", self.0)
+    }
+}
+impl Error for TooFewBasins {}
+
+#[derive(Debug)]
+pub struct FloorHeights(Grid<u8>);
+impl FloorHeights {
+    pub fn low_points(&self) -> Vec<Point> {
+        self.0
+            .points()
+            .filter(|point| {
+                let current = self.0[*point];
+                self.0
+                    .neighbours(*point)
+                    .all(|neighbour| self.0[neighbour] > current)
+            })
+            .collect()
+    }
+    pub fn basin_size_at(&self, point: Point) -> i32 {
+        let mut visited = Grid::new(self.0.rows(), self.0.cols(), false);
+        let mut queue = VecDeque::new();
+        queue.push_back(point);
+
+        while let Some(point) = queue.pop_front() {
+            if self.0[point] == 9 || visited[point] {
+                continue;
+            }
+            visited[point] = true;
+
+            queue.extend(
+                self.0
+                    .neighbours(point)
+                    .filter(|neighbour| !visited[*neighbour]),
+            );
+        }
+
+        visited.iter().filter(|location| **location).count() as i32
+    }
+}
+impl FromStr for FloorHeights {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self(s.parse()?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{first, second, FloorHeights, TooFewBasins};
+
+    fn load_test_data() -> FloorHeights {
+        include_str!("test.txt").parse().unwrap()
+    }
+
+    #[test]
+    fn day9_first() {
+        let input = load_test_data();
+        assert_eq!(first(&input), 15);
+    }
+
+    #[test]
+    fn day9_second() {
+        let input = load_test_data();
+        assert_eq!(second(&input), Ok(1134));
+    }
+
+    #[test]
+    fn day9_too_few_basins() {
+        let input: FloorHeights = "191\n999\n".parse().unwrap();
+        assert_eq!(first(&input), 4);
+        assert_eq!(second(&input), Err(TooFewBasins(2)));
+    }
+}
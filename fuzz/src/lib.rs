@@ -0,0 +1,21 @@
+use common::Solver;
+
+/// Parses `input` as the input of `S`, which must either succeed or return an error pointing
+/// within `input`, and never panic.
+pub fn parse<S: Solver>(input: &str) {
+    if let Err(err) = S::parse(input) {
+        let lines: Vec<&str> = input.lines().collect();
+        assert!(
+            (1..=lines.len().max(1)).contains(&err.line),
+            "line out of range: {:?}",
+            err
+        );
+
+        let line = lines.get(err.line - 1).copied().unwrap_or("");
+        assert!(
+            (1..=line.chars().count() + 1).contains(&err.column),
+            "column out of range: {:?}",
+            err
+        );
+    }
+}
@@ -0,0 +1,248 @@
+use std::{
+    fmt::Display,
+    ops::{Index, IndexMut},
+    str::FromStr,
+};
+
+use common::{parse_lines, ParseError};
+
+const ORTHOGONAL: [(isize, isize); 4] = [(-1, 0), (1, 0), (0, -1), (0, 1)];
+const DIAGONAL: [(isize, isize); 4] = [(-1, -1), (-1, 1), (1, -1), (1, 1)];
+
+/// A cell of a [`Grid`], counting rows down and columns right from the top left corner.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point {
+    pub row: usize,
+    pub col: usize,
+}
+impl Point {
+    pub fn new(row: usize, col: usize) -> Self {
+        Self { row, col }
+    }
+}
+
+/// A rectangular grid stored row by row in a single `Vec`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    rows: usize,
+    cols: usize,
+    cells: Vec<T>,
+}
+impl<T> Grid<T> {
+    pub fn new(rows: usize, cols: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            rows,
+            cols,
+            cells: vec![value; rows * cols],
+        }
+    }
+    pub fn from_fn(rows: usize, cols: usize, mut f: impl FnMut(Point) -> T) -> Self {
+        let cells = (0..rows)
+            .flat_map(|row| (0..cols).map(move |col| Point::new(row, col)))
+            .map(&mut f)
+            .collect();
+        Self { rows, cols, cells }
+    }
+    /// Creates a grid from its rows, or `None` if the rows are not all the same length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Option<Self> {
+        let cols = rows.first().map(Vec::len).unwrap_or(0);
+        if rows.iter().any(|row| row.len() != cols) {
+            return None;
+        }
+
+        Some(Self {
+            rows: rows.len(),
+            cols,
+            cells: rows.into_iter().flatten().collect(),
+        })
+    }
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+    pub fn contains(&self, point: Point) -> bool {
+        point.row < self.rows && point.col < self.cols
+    }
+    pub fn get(&self, point: Point) -> Option<&T> {
+        if self.contains(point) {
+            Some(&self.cells[point.row * self.cols + point.col])
+        } else {
+            None
+        }
+    }
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        if self.contains(point) {
+            Some(&mut self.cells[point.row * self.cols + point.col])
+        } else {
+            None
+        }
+    }
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.cells.iter()
+    }
+    pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, T> {
+        self.cells.iter_mut()
+    }
+    /// Every point of the grid, row by row.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let cols = self.cols;
+        (0..self.rows).flat_map(move |row| (0..cols).map(move |col| Point::new(row, col)))
+    }
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.cols..(row + 1) * self.cols]
+    }
+    pub fn row_mut(&mut self, row: usize) -> &mut [T] {
+        &mut self.cells[row * self.cols..(row + 1) * self.cols]
+    }
+    pub fn column(&self, col: usize) -> impl Iterator<Item = &T> {
+        assert!(col < self.cols, "column {} out of bounds", col);
+        self.cells.iter().skip(col).step_by(self.cols)
+    }
+    pub fn row_slices(&self) -> impl Iterator<Item = &[T]> {
+        // `chunks_exact` panics on a chunk size of 0, and a grid without columns has no cells
+        self.cells.chunks_exact(self.cols.max(1))
+    }
+    /// The up to 4 points above, below, left and right of `point`.
+    pub fn neighbours(&self, point: Point) -> impl Iterator<Item = Point> {
+        self.offsets(point, ORTHOGONAL.iter())
+    }
+    /// The up to 8 points around `point`, including diagonals.
+    pub fn all_neighbours(&self, point: Point) -> impl Iterator<Item = Point> {
+        self.offsets(point, ORTHOGONAL.iter().chain(DIAGONAL.iter()))
+    }
+    fn offsets(
+        &self,
+        point: Point,
+        offsets: impl Iterator<Item = &'static (isize, isize)>,
+    ) -> impl Iterator<Item = Point> {
+        let (rows, cols) = (self.rows, self.cols);
+        offsets.filter_map(move |(row, col)| {
+            let row = point
+                .row
+                .checked_add_signed(*row)
+                .filter(|row| *row < rows)?;
+            let col = point
+                .col
+                .checked_add_signed(*col)
+                .filter(|col| *col < cols)?;
+            Some(Point::new(row, col))
+        })
+    }
+}
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &Self::Output {
+        self.get(point)
+            .unwrap_or_else(|| panic!("{:?} out of bounds", point))
+    }
+}
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, point: Point) -> &mut Self::Output {
+        self.get_mut(point)
+            .unwrap_or_else(|| panic!("{:?} out of bounds", point))
+    }
+}
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.row_slices() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+/// Parses a grid of single digits such as `2199943210`, one row per line.
+impl FromStr for Grid<u8> {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        const EXPECTED: &str = "rows of digits of the same length such as `2199943210`";
+
+        let mut cols = None;
+        let rows = parse_lines(s, |line| {
+            let row = line.trim_end();
+            let digits = row
+                .char_indices()
+                .map(|(i, chr)| match chr.to_digit(10) {
+                    Some(digit) => Ok(digit as u8),
+                    None => {
+                        let chr = &row[i..i + chr.len_utf8()];
+                        Err(ParseError::new(line, chr, "Not a digit", EXPECTED))
+                    }
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            if *cols.get_or_insert(digits.len()) != digits.len() {
+                return Err(ParseError::new(
+                    line,
+                    row,
+                    "Inconsistent row length",
+                    EXPECTED,
+                ));
+            }
+
+            Ok(digits)
+        })?;
+
+        if rows.is_empty() {
+            return Err(ParseError::at_end_of(s, "Empty grid", EXPECTED));
+        }
+        Ok(Self::from_rows(rows).expect("rows have the same length"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Grid, Point};
+
+    #[test]
+    fn neighbours() {
+        let grid = Grid::new(3, 4, 0);
+        let mut corner: Vec<Point> = grid.neighbours(Point::new(0, 0)).collect();
+        corner.sort();
+        assert_eq!(corner, vec![Point::new(0, 1), Point::new(1, 0)]);
+        assert_eq!(grid.all_neighbours(Point::new(0, 0)).count(), 3);
+        assert_eq!(grid.neighbours(Point::new(1, 1)).count(), 4);
+        assert_eq!(grid.all_neighbours(Point::new(1, 1)).count(), 8);
+        assert_eq!(grid.all_neighbours(Point::new(2, 3)).count(), 3);
+        assert!(grid
+            .all_neighbours(Point::new(2, 3))
+            .all(|point| grid.contains(point)));
+    }
+
+    #[test]
+    fn digit_grid() {
+        let grid: Grid<u8> = "219\n398\n".parse().unwrap();
+        assert_eq!((grid.rows(), grid.cols()), (2, 3));
+        assert_eq!(grid[Point::new(1, 2)], 8);
+        assert_eq!(grid.row(1), &[3, 9, 8]);
+        assert_eq!(grid.column(1).copied().collect::<Vec<_>>(), vec![1, 9]);
+        assert_eq!(grid.to_string(), "219\n398\n");
+        assert_eq!(grid.get(Point::new(2, 0)), None);
+
+        let err = "219\n3x8".parse::<Grid<u8>>().unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (2, 2, "x"));
+        let err = "219\n39".parse::<Grid<u8>>().unwrap_err();
+        assert_eq!(
+            (err.line, err.message.as_str()),
+            (2, "Inconsistent row length")
+        );
+        let err = " \n\n".parse::<Grid<u8>>().unwrap_err();
+        assert_eq!((err.line, err.column), (1, 1));
+    }
+
+    #[test]
+    fn from_fn() {
+        let grid = Grid::from_fn(2, 3, |point| point.row * 10 + point.col);
+        assert_eq!(grid.row(1), &[10, 11, 12]);
+        assert_eq!(grid.points().last(), Some(Point::new(1, 2)));
+        assert_eq!(Grid::from_rows(vec![vec![1, 2], vec![3]]), None);
+    }
+}